@@ -99,7 +99,9 @@ impl Events for Service {
 }
 
 fn recording_filename() -> String {
-    let output = obs::frontend::recording::output();
+    let Some(output) = obs::frontend::recording::output() else {
+        return String::new();
+    };
     let settings = output.settings();
 
     settings
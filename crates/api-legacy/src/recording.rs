@@ -16,8 +16,9 @@ impl Recording for Service {
         Ok(Response::new(StatusReply {
             is_recording: recording::active(),
             is_recording_paused: recording::paused(),
-            record_timecode: recording::active()
-                .then(|| common::ns_to_timestamp(common::recording_time(&recording::output()))),
+            record_timecode: recording::output()
+                .filter(|_| recording::active())
+                .map(|output| common::ns_to_timestamp(common::recording_time(&output))),
             recording_filename: recording_filename().unwrap_or_default(),
         }))
     }
@@ -98,7 +99,7 @@ impl Recording for Service {
 }
 
 fn recording_filename() -> Option<String> {
-    let output = recording::output();
+    let output = recording::output()?;
     output
         .active()
         .then(|| {
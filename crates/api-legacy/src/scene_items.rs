@@ -156,14 +156,15 @@ impl SceneItems for Service {
 
         let id = graphics::scoped(|| {
             scene.atomic_update(|scene| {
-                let item = scene.add(&source);
+                let item = scene.add(&source)?;
                 if let Some(visible) = set_visible {
                     item.set_visible(visible);
                 }
 
-                item.id()
+                Some(item.id())
             })
-        });
+        })
+        .ok_or_else(|| Status::internal("failed adding source to scene"))?;
 
         Ok(Response::new(AddReply { id }))
     }
@@ -182,13 +183,14 @@ impl SceneItems for Service {
 
         let (name, id) = graphics::scoped(|| {
             to_scene.atomic_update(|scene| {
-                let item = scene.add(&ref_item.source());
+                let item = scene.add(&ref_item.source())?;
                 item.set_visible(ref_item.visible());
                 let name = item.source().name();
 
-                (name, item.id())
+                Some((name, item.id()))
             })
-        });
+        })
+        .ok_or_else(|| Status::failed_precondition("can't add scene to itself"))?;
 
         Ok(Response::new(DuplicateReply {
             scene: request.to_scene,
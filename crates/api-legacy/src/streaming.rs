@@ -79,10 +79,12 @@ impl Streaming for Service {
             recording_paused: recording::paused(),
             virtualcam: virtualcam::active(),
             preview_only: false,
-            stream_timecode: streaming::active()
-                .then(|| common::ns_to_timestamp(common::recording_time(&streaming::output()))),
-            rec_timecode: recording::active()
-                .then(|| common::ns_to_timestamp(common::recording_time(&recording::output()))),
+            stream_timecode: streaming::output()
+                .filter(|_| streaming::active())
+                .map(|output| common::ns_to_timestamp(common::recording_time(&output))),
+            rec_timecode: recording::output()
+                .filter(|_| recording::active())
+                .map(|output| common::ns_to_timestamp(common::recording_time(&output))),
             virtualcam_timecode: virtualcam::active()
                 .then(|| common::ns_to_timestamp(common::recording_time(&virtualcam::output()))),
         }))
@@ -130,8 +132,9 @@ impl Streaming for Service {
                 ty.as_str(),
                 CUSTOM_SERVICE_ID,
                 &settings.into(),
-                &hotkeys::save_service(&service),
-            );
+                hotkeys::save_service(&service).as_ref(),
+            )
+            .ok_or_else(|| Status::internal("failed creating the stream service"))?;
             streaming::set_service(&service);
         } else {
             let current = service.settings();
@@ -174,7 +177,9 @@ impl Streaming for Service {
     ) -> Result<Response<()>, Status> {
         let text = request.into_inner().text;
 
-        streaming::output().output_caption_text2(&text, 0.0);
+        if let Some(output) = streaming::output() {
+            output.output_caption_text2(&text, 0.0);
+        }
         Ok(Response::new(()))
     }
 }
@@ -0,0 +1,172 @@
+use obs::{
+    callback::calldata::Calldata,
+    frontend::{
+        events::{self, Event as ObsEvent, Handle},
+        preview_mode, profiles, replay_buffer, scene_collections, scenes, transitions,
+    },
+};
+
+use super::{
+    config_event, event, general_event,
+    outputs_event::{self, State},
+    scenes_event, send, transitions_event, Sender,
+};
+use crate::util;
+
+#[must_use]
+pub fn add_callback(tx: Sender) -> Handle {
+    events::add_callback(move |event| {
+        if let Some(value) = convert(event) {
+            send(&tx, value);
+        }
+    })
+}
+
+#[allow(clippy::match_same_arms)]
+fn convert(event: ObsEvent) -> Option<event::Value> {
+    Some(match event {
+        ObsEvent::StreamingStarting => stream_state(false, State::Starting),
+        ObsEvent::StreamingStarted => stream_state(true, State::Started),
+        ObsEvent::StreamingStopping => stream_state(true, State::Stopping),
+        ObsEvent::StreamingStopped => stream_state(false, State::Stopped),
+        ObsEvent::RecordingStarting => record_state(false, State::Starting),
+        ObsEvent::RecordingStarted => record_state(true, State::Started),
+        ObsEvent::RecordingStopping => record_state(true, State::Stopping),
+        ObsEvent::RecordingStopped => record_state(false, State::Stopped),
+        ObsEvent::RecordingPaused => record_state(true, State::Paused),
+        ObsEvent::RecordingUnpaused => record_state(true, State::Resumed),
+        ObsEvent::ReplayBufferStarting => replay_buffer_state(false, State::Starting),
+        ObsEvent::ReplayBufferStarted => replay_buffer_state(true, State::Started),
+        ObsEvent::ReplayBufferStopping => replay_buffer_state(true, State::Stopping),
+        ObsEvent::ReplayBufferStopped => replay_buffer_state(false, State::Stopped),
+        ObsEvent::ReplayBufferSaved => {
+            outputs_event::Value::ReplayBufferSaved(outputs_event::ReplayBufferSaved {
+                path: last_replay()?,
+            })
+            .into()
+        }
+        ObsEvent::VirtualcamStarted => virtual_cam_state(true, State::Started),
+        ObsEvent::VirtualcamStopped => virtual_cam_state(false, State::Stopped),
+        ObsEvent::SceneChanged => {
+            scenes_event::Value::CurrentChanged(scenes_event::CurrentChanged {
+                name: scenes::current().name(),
+            })
+            .into()
+        }
+        ObsEvent::PreviewSceneChanged => {
+            scenes_event::Value::CurrentPreviewChanged(scenes_event::CurrentPreviewChanged {
+                name: scenes::current_preview()?.name(),
+            })
+            .into()
+        }
+        ObsEvent::SceneListChanged => {
+            scenes_event::Value::ListReindexed(scenes_event::ListReindexed {
+                scenes: scenes::list()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, scene)| scenes_event::list_reindexed::Scene {
+                        name: scene.name(),
+                        index: i as i32,
+                        group: scene.is_group(),
+                    })
+                    .collect(),
+            })
+            .into()
+        }
+        ObsEvent::TransitionChanged => {
+            transitions_event::Value::CurrentChanged(transitions_event::CurrentChanged {
+                name: transitions::current().name(),
+            })
+            .into()
+        }
+        ObsEvent::TransitionDurationChanged => transitions_event::Value::CurrentDurationChanged(
+            transitions_event::CurrentDurationChanged {
+                duration: Some(util::duration_to_proto(transitions::duration())),
+            },
+        )
+        .into(),
+        ObsEvent::SceneCollectionChanged => config_event::Value::CurrentSceneCollectionChanged(
+            config_event::CurrentSceneCollectionChanged {
+                name: scene_collections::current(),
+            },
+        )
+        .into(),
+        ObsEvent::SceneCollectionListChanged | ObsEvent::SceneCollectionRenamed => {
+            config_event::Value::SceneCollectionListChanged(
+                config_event::SceneCollectionListChanged {
+                    collections: scene_collections::list(),
+                },
+            )
+            .into()
+        }
+        ObsEvent::ProfileChanged => {
+            config_event::Value::CurrentProfileChanged(config_event::CurrentProfileChanged {
+                name: profiles::current(),
+            })
+            .into()
+        }
+        ObsEvent::ProfileListChanged | ObsEvent::ProfileRenamed => {
+            config_event::Value::ProfileListChanged(config_event::ProfileListChanged {
+                profiles: profiles::list(),
+            })
+            .into()
+        }
+        ObsEvent::Exit => general_event::Value::ExitStarted(general_event::ExitStarted {}).into(),
+        ObsEvent::StudioModeEnabled | ObsEvent::StudioModeDisabled => {
+            general_event::Value::StudioModeStateChanged(general_event::StudioModeStateChanged {
+                enabled: preview_mode::active(),
+            })
+            .into()
+        }
+        ObsEvent::TransitionStopped => return None,
+        ObsEvent::TransitionListChanged => return None,
+        ObsEvent::SceneCollectionCleanup => return None,
+        ObsEvent::FinishedLoading => return None,
+        ObsEvent::TBarValueChanged => return None,
+        ObsEvent::SceneCollectionChanging => return None,
+        ObsEvent::ProfileChanging => return None,
+        ObsEvent::ScriptingShutdown => return None,
+        ObsEvent::Unknown(_) => return None,
+    })
+}
+
+fn stream_state(active: bool, state: State) -> event::Value {
+    outputs_event::Value::StreamStateChanged(outputs_event::StreamStateChanged {
+        active,
+        state: state.into(),
+    })
+    .into()
+}
+
+fn record_state(active: bool, state: State) -> event::Value {
+    outputs_event::Value::RecordStateChanged(outputs_event::RecordStateChanged {
+        active,
+        state: state.into(),
+    })
+    .into()
+}
+
+fn replay_buffer_state(active: bool, state: State) -> event::Value {
+    outputs_event::Value::ReplayBufferStateChanged(outputs_event::ReplayBufferStateChanged {
+        active,
+        state: state.into(),
+    })
+    .into()
+}
+
+fn virtual_cam_state(active: bool, state: State) -> event::Value {
+    outputs_event::Value::VirtualCamStateChanged(outputs_event::VirtualCamStateChanged {
+        active,
+        state: state.into(),
+    })
+    .into()
+}
+
+fn last_replay() -> Option<String> {
+    let output = replay_buffer::output()?;
+    let mut handler = output.proc_handler();
+    let mut calldata = Calldata::default();
+
+    handler.call("get_last_replay", &mut calldata);
+    calldata.string("path")
+}
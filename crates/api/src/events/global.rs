@@ -0,0 +1,190 @@
+use log::warn;
+use obs::{
+    callback::{
+        calldata::Calldata,
+        signal::{GlobalSignal, Handle, SignalHandler},
+    },
+    source::{Source, SourceType},
+};
+
+use super::{
+    event, filters_event, inputs_event, scenes_event, send, tracker::SourceTracker,
+    transitions_event, Sender,
+};
+
+const SIGNALS: &[GlobalSignal] = &[
+    GlobalSignal::SourceCreate,
+    GlobalSignal::SourceDestroy,
+    GlobalSignal::SourceRemove,
+    GlobalSignal::SourceRename,
+    GlobalSignal::SourceActivate,
+    GlobalSignal::SourceDeactivate,
+    GlobalSignal::SourceShow,
+    GlobalSignal::SourceHide,
+    GlobalSignal::SourceVolume,
+    GlobalSignal::SourceTransitionStart,
+    GlobalSignal::SourceTransitionStop,
+];
+
+/// Connect to the global signals, converting them into events and keeping the `tracker` updated
+/// about created and destroyed sources.
+#[must_use]
+pub fn connect_signals(tx: &Sender, tracker: &SourceTracker) -> Vec<Handle> {
+    let Some(handler) = SignalHandler::<GlobalSignal>::get() else {
+        warn!("global signal handler not available");
+        return Vec::new();
+    };
+
+    SIGNALS
+        .iter()
+        .map(|&signal| {
+            let tx = tx.clone();
+            let tracker = tracker.clone();
+            handler.connect(signal, move |data| {
+                let Some(source) = data.get_source() else {
+                    return;
+                };
+
+                if let Some(value) = convert(signal, &source, data) {
+                    send(&tx, value);
+                }
+
+                match signal {
+                    GlobalSignal::SourceCreate => tracker.track(source),
+                    GlobalSignal::SourceDestroy => tracker.untrack(&source),
+                    _ => {}
+                }
+            })
+        })
+        .collect()
+}
+
+fn convert(signal: GlobalSignal, source: &Source<'_>, data: &Calldata) -> Option<event::Value> {
+    match signal {
+        GlobalSignal::SourceCreate => created(source),
+        GlobalSignal::SourceRemove => removed(source),
+        GlobalSignal::SourceRename => {
+            renamed(source, data.string("prev_name")?, data.string("new_name")?)
+        }
+        GlobalSignal::SourceActivate | GlobalSignal::SourceDeactivate => {
+            (source.ty() == SourceType::Input).then(|| {
+                inputs_event::Value::ActiveStateChanged(inputs_event::ActiveStateChanged {
+                    name: source.name(),
+                    active: matches!(signal, GlobalSignal::SourceActivate),
+                })
+                .into()
+            })
+        }
+        GlobalSignal::SourceShow | GlobalSignal::SourceHide => (source.ty() == SourceType::Input)
+            .then(|| {
+                inputs_event::Value::ShowStateChanged(inputs_event::ShowStateChanged {
+                    name: source.name(),
+                    visible: matches!(signal, GlobalSignal::SourceShow),
+                })
+                .into()
+            }),
+        GlobalSignal::SourceVolume => {
+            let volume = data.float("volume")? as f32;
+
+            Some(
+                inputs_event::Value::VolumeChanged(inputs_event::VolumeChanged {
+                    name: source.name(),
+                    volume_mul: volume.into(),
+                    volume_db: obs::audio::mul_to_db(volume).into(),
+                })
+                .into(),
+            )
+        }
+        GlobalSignal::SourceTransitionStart => Some(
+            transitions_event::Value::Started(transitions_event::Started {
+                name: source.name(),
+            })
+            .into(),
+        ),
+        GlobalSignal::SourceTransitionStop => Some(
+            transitions_event::Value::Ended(transitions_event::Ended {
+                name: source.name(),
+            })
+            .into(),
+        ),
+        _ => None,
+    }
+}
+
+fn created(source: &Source<'_>) -> Option<event::Value> {
+    let name = source.name();
+    let kind = source.id();
+
+    Some(match source.ty() {
+        SourceType::Input => inputs_event::Value::Created(inputs_event::Created {
+            name,
+            unversioned_kind: source.unversioned_id(),
+            settings: source.settings().to_json(),
+            default_settings: default_settings(&kind),
+            kind,
+        })
+        .into(),
+        SourceType::Filter => filters_event::Value::Created(filters_event::Created {
+            name,
+            settings: source.settings().to_json(),
+            default_settings: default_settings(&kind),
+            kind,
+        })
+        .into(),
+        SourceType::Transition => transitions_event::Value::Created(transitions_event::Created {
+            name,
+            kind,
+            fixed: source.transition_fixed(),
+        })
+        .into(),
+        SourceType::Scene => scenes_event::Value::Created(scenes_event::Created {
+            name,
+            group: source.is_group(),
+        })
+        .into(),
+        SourceType::Unknown(_) => return None,
+    })
+}
+
+fn removed(source: &Source<'_>) -> Option<event::Value> {
+    let name = source.name();
+
+    Some(match source.ty() {
+        SourceType::Input => inputs_event::Value::Removed(inputs_event::Removed { name }).into(),
+        SourceType::Filter => filters_event::Value::Removed(filters_event::Removed { name }).into(),
+        SourceType::Transition => {
+            transitions_event::Value::Removed(transitions_event::Removed { name }).into()
+        }
+        SourceType::Scene => scenes_event::Value::Removed(scenes_event::Removed {
+            name,
+            group: source.is_group(),
+        })
+        .into(),
+        SourceType::Unknown(_) => return None,
+    })
+}
+
+fn renamed(source: &Source<'_>, old_name: String, name: String) -> Option<event::Value> {
+    Some(match source.ty() {
+        SourceType::Input => {
+            inputs_event::Value::NameChanged(inputs_event::NameChanged { old_name, name }).into()
+        }
+        SourceType::Filter => {
+            filters_event::Value::NameChanged(filters_event::NameChanged { old_name, name }).into()
+        }
+        SourceType::Transition => {
+            transitions_event::Value::NameChanged(transitions_event::NameChanged { old_name, name })
+                .into()
+        }
+        SourceType::Scene => {
+            scenes_event::Value::NameChanged(scenes_event::NameChanged { old_name, name }).into()
+        }
+        SourceType::Unknown(_) => return None,
+    })
+}
+
+fn default_settings(kind: &str) -> String {
+    obs::source::defaults(kind)
+        .map(|defaults| defaults.to_json())
+        .unwrap_or_default()
+}
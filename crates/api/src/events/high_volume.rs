@@ -0,0 +1,189 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use obs::{
+    audio::{FaderType, Volmeter, VolmeterLevels},
+    source::{OutputFlags, Source, SourceType},
+};
+
+use super::{
+    high_volume_event::{input_volume_meters, InputVolumeMeters},
+    inputs_event,
+};
+
+/// Volume meter of a single input, that keeps the latest measured levels around until they're
+/// collected.
+struct Meter {
+    _volmeter: Volmeter,
+    sample: Arc<Mutex<Option<Sample>>>,
+}
+
+/// Latest levels of a meter, together with a counter that allows each subscriber to tell whether
+/// it already saw them.
+#[derive(Clone, Copy)]
+struct Sample {
+    seq: u64,
+    levels: VolmeterLevels,
+}
+
+impl Meter {
+    fn new(source: &Source<'_>) -> Option<Self> {
+        let mut volmeter = Volmeter::new(FaderType::Log);
+        if !volmeter.attach_source(source) {
+            return None;
+        }
+
+        let sample = Arc::new(Mutex::new(None::<Sample>));
+        volmeter.add_callback({
+            let sample = Arc::clone(&sample);
+            move |update| {
+                let mut sample = sample.lock().unwrap();
+                *sample = Some(Sample {
+                    seq: sample.map_or(0, |s| s.seq.wrapping_add(1)),
+                    levels: *update,
+                });
+            }
+        });
+
+        Some(Self {
+            _volmeter: volmeter,
+            sample,
+        })
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Amount of active subscribers. The meters are only attached while there is at least one.
+    subscribers: usize,
+    /// Set once OBS shuts down, to not attach any further meters.
+    closed: bool,
+    meters: HashMap<String, Meter>,
+}
+
+/// Volume meters for all inputs that provide audio, keyed by the input name.
+///
+/// The meters are shared between all subscribers, so each input only gets a single volmeter,
+/// regardless of how many clients listen to the levels. It is cheap to clone.
+#[derive(Clone, Default)]
+pub struct Meters(Arc<Mutex<State>>);
+
+impl Meters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new subscriber, attaching the meters to all audio inputs if it's the first one.
+    pub fn subscribe(&self) -> Subscription {
+        let mut state = self.0.lock().unwrap();
+
+        if state.subscribers == 0 && !state.closed {
+            state.meters = obs::source::list()
+                .iter()
+                .filter(|source| has_audio(source))
+                .filter_map(|source| Some((source.name(), Meter::new(source)?)))
+                .collect();
+        }
+
+        state.subscribers += 1;
+
+        Subscription {
+            meters: self.clone(),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Keep the meters in sync with the current inputs, by reacting to input events.
+    pub fn update(&self, event: &inputs_event::Value) {
+        let mut state = self.0.lock().unwrap();
+        if state.subscribers == 0 || state.closed {
+            return;
+        }
+
+        match event {
+            inputs_event::Value::Created(inputs_event::Created { name, .. }) => {
+                let meter = Source::by_name(name)
+                    .filter(has_audio)
+                    .and_then(|source| Meter::new(&source));
+
+                if let Some(meter) = meter {
+                    state.meters.insert(name.clone(), meter);
+                }
+            }
+            inputs_event::Value::Removed(inputs_event::Removed { name }) => {
+                state.meters.remove(name);
+            }
+            inputs_event::Value::NameChanged(inputs_event::NameChanged { old_name, name }) => {
+                if let Some(meter) = state.meters.remove(old_name) {
+                    state.meters.insert(name.clone(), meter);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Detach all meters for good, as OBS is shutting down.
+    pub fn close(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.closed = true;
+        state.meters.clear();
+    }
+}
+
+/// Handle of a single subscriber to the shared [`Meters`], which remembers the levels it already
+/// received.
+pub struct Subscription {
+    meters: Meters,
+    seen: HashMap<String, u64>,
+}
+
+impl Subscription {
+    /// Take the latest levels of all meters, that were updated since the last call.
+    pub fn take_levels(&mut self) -> Option<InputVolumeMeters> {
+        let state = self.meters.0.lock().unwrap();
+
+        // Forget about removed inputs, so the map doesn't grow indefinitely.
+        self.seen.retain(|name, _| state.meters.contains_key(name));
+
+        let inputs = state
+            .meters
+            .iter()
+            .filter_map(|(name, meter)| {
+                let Sample { seq, levels } = (*meter.sample.lock().unwrap())?;
+                if self.seen.insert(name.clone(), seq) == Some(seq) {
+                    return None;
+                }
+
+                Some(input_volume_meters::Input {
+                    name: name.clone(),
+                    channels: (0..levels.channels())
+                        .map(|i| input_volume_meters::Channel {
+                            magnitude: levels.magnitude()[i],
+                            peak: levels.peak()[i],
+                            input_peak: levels.input_peak()[i],
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        (!inputs.is_empty()).then_some(InputVolumeMeters { inputs })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut state = self.meters.0.lock().unwrap();
+        state.subscribers -= 1;
+
+        if state.subscribers == 0 {
+            state.meters.clear();
+        }
+    }
+}
+
+fn has_audio(source: &Source<'_>) -> bool {
+    source.ty() == SourceType::Input && source.output_flags().contains(OutputFlags::AUDIO)
+}
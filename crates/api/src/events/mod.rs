@@ -0,0 +1,404 @@
+use std::{sync::Arc, time::Duration};
+
+use log::{debug, warn};
+use obs::frontend::scenes;
+use tokio::{
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc, watch, Mutex,
+    },
+    time::{self, MissedTickBehavior},
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+pub use self::events_service_server::EventsServiceServer;
+use self::{high_volume::Meters, tracker::SourceTracker};
+use crate::precondition;
+
+mod frontend;
+mod global;
+mod high_volume;
+mod scene_items;
+mod sources;
+mod tracker;
+
+tonic::include_proto!("events.v1");
+
+/// Amount of events that are buffered for each subscriber, before the oldest ones are skipped.
+const CHANNEL_CAPACITY: usize = 64;
+/// Interval for high volume events, if the client doesn't request a specific one.
+const DEFAULT_HIGH_VOLUME_INTERVAL: Duration = Duration::from_millis(50);
+/// Lower limit for the interval of high volume events, to protect against flooding clients.
+const MIN_HIGH_VOLUME_INTERVAL: Duration = Duration::from_millis(10);
+
+type Sender = broadcast::Sender<Event>;
+
+/// Event bus that connects to all the OBS signals and frontend events once, and distributes them
+/// to any amount of subscribers. It is cheap to clone, so the same instance can be shared between
+/// several servers.
+#[derive(Clone)]
+pub struct EventsService {
+    tx: Arc<Mutex<Option<Sender>>>,
+    meters: Meters,
+}
+
+impl EventsService {
+    #[must_use]
+    pub fn new(mut signal: watch::Receiver<()>) -> Self {
+        let (tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+        let tracker = SourceTracker::new(tx.clone());
+        let meters = Meters::new();
+
+        // Connect the global signals first, so no source created in the meantime is missed.
+        let event_handle = frontend::add_callback(tx.clone());
+        let handles = global::connect_signals(&tx, &tracker);
+
+        debug!("connecting source signals...");
+
+        for source in obs::source::list().into_iter().chain(scenes::list()) {
+            tracker.track(source);
+        }
+
+        debug!("all source signals connected");
+
+        // Keep the shared volume meters in sync with the inputs, for all high volume subscribers.
+        tokio::spawn({
+            let mut event_rx = tx.subscribe();
+            let meters = meters.clone();
+
+            async move {
+                loop {
+                    match event_rx.recv().await {
+                        Ok(Event {
+                            value: Some(event::Value::Inputs(InputsEvent { value: Some(event) })),
+                        }) => meters.update(&event),
+                        Ok(_) | Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            }
+        });
+
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        let tx2 = Arc::clone(&tx);
+        let meters2 = meters.clone();
+
+        tokio::spawn(async move {
+            signal.changed().await.ok();
+            drop(event_handle);
+            // Disconnect the global signals before the per-source ones, so the tracker doesn't
+            // pick up any new sources while being cleared.
+            drop(handles);
+
+            tokio::task::spawn_blocking(move || tracker.clear())
+                .await
+                .ok();
+            meters2.close();
+
+            tx2.lock().await.take();
+        });
+
+        Self { tx, meters }
+    }
+
+    async fn subscribe(&self) -> Result<broadcast::Receiver<Event>, Status> {
+        self.tx
+            .lock()
+            .await
+            .as_ref()
+            .map(broadcast::Sender::subscribe)
+            .ok_or_else(|| Status::aborted("server shutting down"))
+    }
+
+    /// Publish a custom event, sent by a client, to all subscribers of the general events.
+    pub(crate) async fn broadcast(&self, content: prost_types::Struct) -> Result<(), Status> {
+        let tx = self.tx.lock().await;
+        let tx = tx
+            .as_ref()
+            .ok_or_else(|| Status::aborted("server shutting down"))?;
+
+        send(
+            tx,
+            general_event::Value::Custom(general_event::Custom {
+                content: Some(content),
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Subscribe to the event bus and forward all events, that the `filter` keeps, to a new
+    /// stream.
+    async fn listen<T, F>(
+        &self,
+        filter: F,
+    ) -> Result<Response<ReceiverStream<Result<T, Status>>>, Status>
+    where
+        T: Send + 'static,
+        F: Fn(event::Value) -> Option<T> + Send + 'static,
+    {
+        let mut event_rx = self.subscribe().await?;
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            loop {
+                let value = match event_rx.recv().await {
+                    Ok(Event { value: Some(value) }) => value,
+                    Ok(Event { value: None }) => continue,
+                    Err(RecvError::Lagged(count)) => {
+                        warn!("subscriber lagged behind, skipped {count} events");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                let Some(response) = filter(value) else {
+                    continue;
+                };
+
+                if tx.send(Ok(response)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+#[tonic::async_trait]
+impl events_service_server::EventsService for EventsService {
+    type AllStream = ReceiverStream<Result<AllResponse, Status>>;
+    type ConfigStream = ReceiverStream<Result<ConfigResponse, Status>>;
+    type FiltersStream = ReceiverStream<Result<FiltersResponse, Status>>;
+    type GeneralStream = ReceiverStream<Result<GeneralResponse, Status>>;
+    type HighVolumeStream = ReceiverStream<Result<HighVolumeResponse, Status>>;
+    type InputsStream = ReceiverStream<Result<InputsResponse, Status>>;
+    type MediaInputsStream = ReceiverStream<Result<MediaInputsResponse, Status>>;
+    type OutputsStream = ReceiverStream<Result<OutputsResponse, Status>>;
+    type SceneItemsStream = ReceiverStream<Result<SceneItemsResponse, Status>>;
+    type ScenesStream = ReceiverStream<Result<ScenesResponse, Status>>;
+    type TransitionsStream = ReceiverStream<Result<TransitionsResponse, Status>>;
+
+    async fn all(&self, request: Request<AllRequest>) -> Result<Response<Self::AllStream>, Status> {
+        let AllRequest {} = request.into_inner();
+
+        self.listen(|value| {
+            Some(AllResponse {
+                event: Some(Event { value: Some(value) }),
+            })
+        })
+        .await
+    }
+
+    async fn general(
+        &self,
+        request: Request<GeneralRequest>,
+    ) -> Result<Response<Self::GeneralStream>, Status> {
+        let GeneralRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::General(event) => Some(GeneralResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn config(
+        &self,
+        request: Request<ConfigRequest>,
+    ) -> Result<Response<Self::ConfigStream>, Status> {
+        let ConfigRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::Config(event) => Some(ConfigResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn scenes(
+        &self,
+        request: Request<ScenesRequest>,
+    ) -> Result<Response<Self::ScenesStream>, Status> {
+        let ScenesRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::Scenes(event) => Some(ScenesResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn inputs(
+        &self,
+        request: Request<InputsRequest>,
+    ) -> Result<Response<Self::InputsStream>, Status> {
+        let InputsRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::Inputs(event) => Some(InputsResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn transitions(
+        &self,
+        request: Request<TransitionsRequest>,
+    ) -> Result<Response<Self::TransitionsStream>, Status> {
+        let TransitionsRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::Transitions(event) => Some(TransitionsResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn filters(
+        &self,
+        request: Request<FiltersRequest>,
+    ) -> Result<Response<Self::FiltersStream>, Status> {
+        let FiltersRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::Filters(event) => Some(FiltersResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn outputs(
+        &self,
+        request: Request<OutputsRequest>,
+    ) -> Result<Response<Self::OutputsStream>, Status> {
+        let OutputsRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::Outputs(event) => Some(OutputsResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn scene_items(
+        &self,
+        request: Request<SceneItemsRequest>,
+    ) -> Result<Response<Self::SceneItemsStream>, Status> {
+        let SceneItemsRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::SceneItems(event) => Some(SceneItemsResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn media_inputs(
+        &self,
+        request: Request<MediaInputsRequest>,
+    ) -> Result<Response<Self::MediaInputsStream>, Status> {
+        let MediaInputsRequest {} = request.into_inner();
+
+        self.listen(|value| match value {
+            event::Value::MediaInputs(event) => Some(MediaInputsResponse { event: Some(event) }),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn high_volume(
+        &self,
+        request: Request<HighVolumeRequest>,
+    ) -> Result<Response<Self::HighVolumeStream>, Status> {
+        let HighVolumeRequest { interval } = request.into_inner();
+
+        let interval = interval
+            .map(Duration::try_from)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("invalid interval: {e:?}")))?
+            .unwrap_or(DEFAULT_HIGH_VOLUME_INTERVAL);
+        precondition!(
+            interval >= MIN_HIGH_VOLUME_INTERVAL,
+            "interval must be at least {}ms",
+            MIN_HIGH_VOLUME_INTERVAL.as_millis()
+        );
+
+        let mut event_rx = self.subscribe().await?;
+        let mut meters = self.meters.subscribe();
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let mut ticker = time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        let Some(levels) = meters.take_levels() else {
+                            continue;
+                        };
+
+                        let response = HighVolumeResponse {
+                            event: Some(HighVolumeEvent {
+                                value: Some(high_volume_event::Value::InputVolumeMeters(levels)),
+                            }),
+                        };
+
+                        if tx.send(Ok(response)).await.is_err() {
+                            break;
+                        }
+                    }
+                    // The shared meters are kept up to date elsewhere, the events are only observed
+                    // to notice the shutdown.
+                    event = event_rx.recv() => match event {
+                        Ok(_) | Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => break,
+                    },
+                    // Without any levels to send, a gone client would otherwise never be noticed
+                    // and keep the meters attached.
+                    () = tx.closed() => break,
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Wrap a single event into the top level event message and publish it to all subscribers.
+fn send(tx: &Sender, value: impl Into<event::Value>) {
+    // Sending only fails if there are no subscribers at the moment, which is fine.
+    tx.send(Event {
+        value: Some(value.into()),
+    })
+    .ok();
+}
+
+macro_rules! impl_into_event {
+    ($($module:ident => $variant:ident($event:ident)),+ $(,)?) => {
+        $(
+            impl From<$module::Value> for event::Value {
+                fn from(value: $module::Value) -> Self {
+                    Self::$variant($event { value: Some(value) })
+                }
+            }
+        )+
+    };
+}
+
+impl_into_event! {
+    general_event => General(GeneralEvent),
+    config_event => Config(ConfigEvent),
+    scenes_event => Scenes(ScenesEvent),
+    inputs_event => Inputs(InputsEvent),
+    transitions_event => Transitions(TransitionsEvent),
+    filters_event => Filters(FiltersEvent),
+    outputs_event => Outputs(OutputsEvent),
+    scene_items_event => SceneItems(SceneItemsEvent),
+    media_inputs_event => MediaInputs(MediaInputsEvent),
+    high_volume_event => HighVolume(HighVolumeEvent),
+}
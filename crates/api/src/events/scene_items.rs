@@ -0,0 +1,114 @@
+use log::warn;
+use obs::{
+    callback::signal::{Handle, SceneCalldata, SceneSignal},
+    scene::{Scene, SceneItem},
+};
+
+use super::{event, scene_items_event, send, Sender};
+
+const SIGNALS: &[SceneSignal] = &[
+    SceneSignal::ItemAdd,
+    SceneSignal::ItemRemove,
+    SceneSignal::Reorder,
+    SceneSignal::ItemVisible,
+    SceneSignal::ItemLocked,
+    SceneSignal::ItemTransform,
+];
+
+#[must_use]
+pub fn connect_signals(scene: &Scene<'_>, tx: &Sender) -> Vec<Handle> {
+    let handler = scene.signal_handler();
+
+    SIGNALS
+        .iter()
+        .map(|&signal| {
+            let tx = tx.clone();
+            handler.connect(signal, move |data| {
+                match SceneCalldata::try_from((signal, data)) {
+                    Ok(data) => {
+                        if let Some(value) = convert(data) {
+                            send(&tx, value);
+                        }
+                    }
+                    Err(e) => warn!("{e:?}"),
+                }
+            })
+        })
+        .collect()
+}
+
+fn convert(data: SceneCalldata<'_>) -> Option<event::Value> {
+    Some(match data {
+        SceneCalldata::ItemAdd(scene, item) => {
+            scene_items_event::Value::Created(scene_items_event::Created {
+                scene: scene.source().name(),
+                input: item.source().name(),
+                id: item.id(),
+                index: index_of(&scene, &item),
+            })
+            .into()
+        }
+        SceneCalldata::ItemRemove(scene, item) => {
+            scene_items_event::Value::Removed(scene_items_event::Removed {
+                scene: scene.source().name(),
+                input: item.source().name(),
+                id: item.id(),
+                index: index_of(&scene, &item),
+            })
+            .into()
+        }
+        SceneCalldata::Reorder(scene) => {
+            scene_items_event::Value::ListReindexed(scene_items_event::ListReindexed {
+                scene: scene.source().name(),
+                items: scene
+                    .list_items()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| scene_items_event::list_reindexed::SceneItem {
+                        id: item.id(),
+                        index: i as i32,
+                    })
+                    .collect(),
+            })
+            .into()
+        }
+        SceneCalldata::ItemVisible {
+            scene,
+            item,
+            visible,
+        } => scene_items_event::Value::EnableStateChanged(scene_items_event::EnableStateChanged {
+            scene: scene.source().name(),
+            id: item.id(),
+            enabled: visible,
+        })
+        .into(),
+        SceneCalldata::ItemLocked {
+            scene,
+            item,
+            locked,
+        } => scene_items_event::Value::LockStateChanged(scene_items_event::LockStateChanged {
+            scene: scene.source().name(),
+            id: item.id(),
+            locked,
+        })
+        .into(),
+        SceneCalldata::ItemTransform(scene, item) => {
+            scene_items_event::Value::TransformChanged(scene_items_event::TransformChanged {
+                scene: scene.source().name(),
+                id: item.id(),
+            })
+            .into()
+        }
+        _ => return None,
+    })
+}
+
+fn index_of(scene: &Scene<'_>, item: &SceneItem<'_>) -> i32 {
+    let id = item.id();
+
+    scene
+        .list_items()
+        .iter()
+        .position(|item| item.id() == id)
+        .map_or(-1, |i| i as i32)
+}
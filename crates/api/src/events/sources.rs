@@ -0,0 +1,139 @@
+use log::warn;
+use obs::{
+    callback::signal::{Handle, SourceCalldata, SourceSignal},
+    source::Source,
+};
+
+use super::{
+    event, filters_event, inputs_event,
+    media_inputs_event::{self, action_triggered::Action},
+    send, Sender,
+};
+
+const SIGNALS: &[SourceSignal] = &[
+    SourceSignal::Mute,
+    SourceSignal::AudioSync,
+    SourceSignal::AudioMixers,
+    SourceSignal::FilterAdd,
+    SourceSignal::FilterRemove,
+    SourceSignal::ReorderFilters,
+    SourceSignal::MediaPlay,
+    SourceSignal::MediaPause,
+    SourceSignal::MediaRestart,
+    SourceSignal::MediaStopped,
+    SourceSignal::MediaNext,
+    SourceSignal::MediaPrevious,
+    SourceSignal::MediaStarted,
+    SourceSignal::MediaEnded,
+];
+
+#[must_use]
+pub fn connect_signals(source: &Source<'_>, tx: &Sender) -> Vec<Handle> {
+    let handler = source.signal_handler();
+
+    SIGNALS
+        .iter()
+        .map(|&signal| {
+            let tx = tx.clone();
+            handler.connect(signal, move |data| {
+                match SourceCalldata::try_from((signal, data)) {
+                    Ok(data) => {
+                        if let Some(value) = convert(data) {
+                            send(&tx, value);
+                        }
+                    }
+                    Err(e) => warn!("{e:?}"),
+                }
+            })
+        })
+        .collect()
+}
+
+fn convert(data: SourceCalldata<'_>) -> Option<event::Value> {
+    Some(match data {
+        SourceCalldata::Mute { source, muted } => {
+            inputs_event::Value::MuteStateChanged(inputs_event::MuteStateChanged {
+                name: source.name(),
+                muted,
+            })
+            .into()
+        }
+        SourceCalldata::AudioSync { source, offset } => {
+            inputs_event::Value::AudioSyncOffsetChanged(inputs_event::AudioSyncOffsetChanged {
+                name: source.name(),
+                offset: offset / 1_000_000,
+            })
+            .into()
+        }
+        SourceCalldata::AudioMixers { source, mixers } => {
+            let track = |i: u32| mixers & (1 << i) != 0;
+
+            inputs_event::Value::AudioTracksChanged(inputs_event::AudioTracksChanged {
+                name: source.name(),
+                track_1: track(0),
+                track_2: track(1),
+                track_3: track(2),
+                track_4: track(3),
+                track_5: track(4),
+                track_6: track(5),
+            })
+            .into()
+        }
+        SourceCalldata::FilterAdd { source, filter } => {
+            let filter = filter.name();
+            let index = source
+                .filters()
+                .iter()
+                .position(|f| f.name() == filter)
+                .unwrap_or_default();
+
+            filters_event::Value::AddedToSource(filters_event::AddedToSource {
+                source: source.name(),
+                filter,
+                index: index as i32,
+            })
+            .into()
+        }
+        SourceCalldata::FilterRemove { source, filter } => {
+            filters_event::Value::RemovedFromSource(filters_event::RemovedFromSource {
+                source: source.name(),
+                filter: filter.name(),
+            })
+            .into()
+        }
+        SourceCalldata::ReorderFilters(source) => {
+            filters_event::Value::SourceListReindexed(filters_event::SourceListReindexed {
+                source: source.name(),
+                filters: source.filters().iter().map(Source::name).collect(),
+            })
+            .into()
+        }
+        SourceCalldata::MediaStarted(source) => {
+            media_inputs_event::Value::PlaybackStarted(media_inputs_event::PlaybackStarted {
+                name: source.name(),
+            })
+            .into()
+        }
+        SourceCalldata::MediaEnded(source) => {
+            media_inputs_event::Value::PlaybackEnded(media_inputs_event::PlaybackEnded {
+                name: source.name(),
+            })
+            .into()
+        }
+        SourceCalldata::MediaPlay(source) => action_triggered(&source, Action::Play),
+        SourceCalldata::MediaPause(source) => action_triggered(&source, Action::Pause),
+        SourceCalldata::MediaRestart(source) => action_triggered(&source, Action::Restart),
+        SourceCalldata::MediaStopped(source) => action_triggered(&source, Action::Stop),
+        SourceCalldata::MediaNext(source) => action_triggered(&source, Action::Next),
+        SourceCalldata::MediaPrevious(source) => action_triggered(&source, Action::Previous),
+        _ => return None,
+    })
+}
+
+fn action_triggered(source: &Source<'_>, action: Action) -> event::Value {
+    media_inputs_event::Value::ActionTriggered(media_inputs_event::ActionTriggered {
+        name: source.name(),
+        action: action.into(),
+    })
+    .into()
+}
@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+use obs::{
+    callback::signal::Handle,
+    scene::Scene,
+    source::{Source, SourceType, WeakSource},
+};
+
+use super::{scene_items, sources, Sender};
+
+/// Keeps the per-source signal handles of all inputs and scenes, so they can be connected when a
+/// source is created and disconnected again once it's destroyed.
+///
+/// Sources are only referenced weakly, to not keep them alive longer than OBS would.
+#[derive(Clone)]
+pub struct SourceTracker {
+    tx: Sender,
+    entries: Arc<Mutex<Vec<(WeakSource, Vec<Handle>)>>>,
+}
+
+impl SourceTracker {
+    pub fn new(tx: Sender) -> Self {
+        Self {
+            tx,
+            entries: Arc::default(),
+        }
+    }
+
+    /// Connect the signals of the given source, unless it is already tracked or its type doesn't
+    /// emit any events that we're interested in.
+    pub fn track(&self, source: Source<'_>) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.iter().any(|(weak, _)| weak.references(&source)) {
+            return;
+        }
+
+        let weak = source.downgrade();
+        let name = source.name();
+
+        if let Some(handles) = connect_signals(source, &self.tx) {
+            debug!("connected signals for `{name}`");
+            entries.push((weak, handles));
+        }
+    }
+
+    /// Disconnect all signals of the given source, if it is tracked.
+    pub fn untrack(&self, source: &Source<'_>) {
+        let entry = {
+            let mut entries = self.entries.lock().unwrap();
+            entries
+                .iter()
+                .position(|(weak, _)| weak.references(source))
+                .map(|i| entries.swap_remove(i))
+        };
+
+        // Dropping the handles disconnects the signals. This happens outside of the lock, to not
+        // block other signal callbacks in the meantime.
+        if entry.is_some() {
+            debug!("disconnected signals for `{}`", source.name());
+        }
+    }
+
+    /// Disconnect the signals of all tracked sources.
+    ///
+    /// Sources that were destroyed without being untracked already freed their signal handler, so
+    /// disconnecting from it would be a use-after-free. Their handles are forgotten instead. Live
+    /// sources are kept alive until their signals are disconnected.
+    pub fn clear(&self) {
+        let entries = std::mem::take(&mut *self.entries.lock().unwrap());
+
+        for (weak, handles) in entries {
+            match weak.upgrade() {
+                Some(_source) => drop(handles),
+                None => std::mem::forget(handles),
+            }
+        }
+    }
+}
+
+fn connect_signals(source: Source<'_>, tx: &Sender) -> Option<Vec<Handle>> {
+    match source.ty() {
+        SourceType::Input => Some(sources::connect_signals(&source, tx)),
+        SourceType::Scene => {
+            let mut handles = sources::connect_signals(&source, tx);
+
+            if let Some(scene) = Scene::from_group_or_scene(source) {
+                handles.extend(scene_items::connect_signals(&scene, tx));
+            }
+
+            Some(handles)
+        }
+        SourceType::Filter | SourceType::Transition | SourceType::Unknown(_) => None,
+    }
+}
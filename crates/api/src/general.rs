@@ -1,11 +1,48 @@
-use obs::libobs_sys::{LIBOBS_API_MAJOR_VER, LIBOBS_API_MINOR_VER, LIBOBS_API_PATCH_VER};
+use std::{sync::Mutex, time::Duration};
+
+use obs::{
+    frontend,
+    libobs_sys::{LIBOBS_API_MAJOR_VER, LIBOBS_API_MINOR_VER, LIBOBS_API_PATCH_VER},
+    os::CpuUsageInfo,
+    video::Video,
+};
+use tokio::{
+    sync::watch,
+    time::{self, Instant},
+};
 use tonic::{Request, Response, Status};
 
 pub use self::general_service_server::GeneralServiceServer;
+use crate::{precondition, util, EventsService, Metrics};
 
 tonic::include_proto!("general.v1");
 
-pub struct GeneralService;
+/// Upper limit for time based sleeps.
+const MAX_SLEEP_DURATION: Duration = Duration::from_secs(50);
+/// Upper limit for frame based sleeps.
+const MAX_SLEEP_FRAMES: u32 = 10_000;
+/// Longest time to wait for the next frame during a frame based sleep, before assuming that
+/// rendering stalled.
+const MAX_FRAME_STALL: Duration = Duration::from_secs(5);
+
+pub struct GeneralService {
+    events: EventsService,
+    metrics: Metrics,
+    cpu_usage: Mutex<CpuUsageInfo>,
+    signal: watch::Receiver<()>,
+}
+
+impl GeneralService {
+    #[must_use]
+    pub fn new(events: EventsService, metrics: Metrics, signal: watch::Receiver<()>) -> Self {
+        Self {
+            events,
+            metrics,
+            cpu_usage: Mutex::new(CpuUsageInfo::new()),
+            signal,
+        }
+    }
+}
 
 #[tonic::async_trait]
 impl general_service_server::GeneralService for GeneralService {
@@ -42,14 +79,48 @@ impl general_service_server::GeneralService for GeneralService {
         &self,
         request: Request<StatsRequest>,
     ) -> Result<Response<StatsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        use self::stats_response::{Grpc, Output, Rendering};
+
+        let StatsRequest {} = request.into_inner();
+
+        let video = Video::get();
+        let directory = util::recording_directory(&frontend::profile_config()).unwrap_or_default();
+
+        Ok(Response::new(StatsResponse {
+            cpu_usage: self.cpu_usage.lock().unwrap().query() as f32,
+            memory_usage: obs::os::memory_usage(),
+            disk_space: obs::os::free_disk_space(&directory),
+            fps: obs::active_fps() as f32,
+            rendering: Some(Rendering {
+                average_frame_time: Some(util::duration_to_proto(obs::Duration::nanoseconds(
+                    obs::average_frame_time_ns() as i64,
+                ))),
+                skipped_frames: obs::lagged_frames().into(),
+                total_frames: obs::total_frames().into(),
+            }),
+            output: Some(Output {
+                skipped_frames: video.skipped_frames().into(),
+                total_frames: video.total_frames().into(),
+            }),
+            grpc: Some(Grpc {
+                connections: self.metrics.connections(),
+                requests: self.metrics.requests(),
+            }),
+        }))
     }
 
     async fn broadcast_event(
         &self,
         request: Request<BroadcastEventRequest>,
     ) -> Result<Response<BroadcastEventResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let BroadcastEventRequest { content } = request.into_inner();
+
+        let content =
+            content.ok_or_else(|| Status::invalid_argument("content must be specified"))?;
+
+        self.events.broadcast(content).await?;
+
+        Ok(Response::new(BroadcastEventResponse {}))
     }
 
     async fn call_vendor(
@@ -63,6 +134,77 @@ impl general_service_server::GeneralService for GeneralService {
         &self,
         request: Request<SleepRequest>,
     ) -> Result<Response<SleepResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        use self::sleep_request::Sleep;
+
+        let SleepRequest { sleep } = request.into_inner();
+
+        // If the client goes away, the whole request future is dropped, which cancels the sleep as
+        // well. Only the server shutdown has to be handled explicitly.
+        let mut signal = self.signal.clone();
+
+        match sleep.ok_or_else(|| Status::invalid_argument("sleep must be specified"))? {
+            Sleep::Duration(duration) => {
+                let duration = Duration::try_from(duration)
+                    .map_err(|e| Status::invalid_argument(format!("invalid duration: {e:?}")))?;
+                precondition!(
+                    duration <= MAX_SLEEP_DURATION,
+                    "duration must be at most {}s",
+                    MAX_SLEEP_DURATION.as_secs()
+                );
+
+                tokio::select! {
+                    () = time::sleep(duration) => {}
+                    _ = signal.changed() => return Err(Status::aborted("server shutting down")),
+                }
+            }
+            Sleep::Frames(frames) => {
+                precondition!(
+                    frames <= MAX_SLEEP_FRAMES,
+                    "frames must be at most {}",
+                    MAX_SLEEP_FRAMES
+                );
+
+                sleep_frames(frames, signal).await?;
+            }
+        }
+
+        Ok(Response::new(SleepResponse {}))
+    }
+}
+
+/// Wait until OBS rendered the given amount of frames.
+///
+/// The render counter is polled several times per frame, so the wait ends shortly after the last
+/// frame was rendered, even if individual frames take longer than the configured frame time. Fails
+/// if the server shuts down or no new frame is rendered for [`MAX_FRAME_STALL`].
+async fn sleep_frames(frames: u32, mut signal: watch::Receiver<()>) -> Result<(), Status> {
+    if frames == 0 {
+        return Ok(());
+    }
+
+    let poll = Duration::from_nanos(Video::get().frame_time() / 4).max(Duration::from_millis(1));
+    let start = obs::total_frames();
+    let mut last = (start, Instant::now());
+
+    loop {
+        let current = obs::total_frames();
+        if current.wrapping_sub(start) >= frames {
+            return Ok(());
+        }
+
+        if current != last.0 {
+            last = (current, Instant::now());
+        } else if last.1.elapsed() >= MAX_FRAME_STALL {
+            return Err(Status::aborted(format!(
+                "no frame rendered within {}s, after {} of {frames} frames",
+                MAX_FRAME_STALL.as_secs(),
+                current.wrapping_sub(start),
+            )));
+        }
+
+        tokio::select! {
+            () = time::sleep(poll) => {}
+            _ = signal.changed() => return Err(Status::aborted("server shutting down")),
+        }
     }
 }
@@ -1,11 +1,22 @@
-use obs::source::{self, OutputFlags, Source, SourceType, Volume};
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use obs::{
+    data::Data,
+    graphics,
+    properties::{ButtonType, ComboFormat, Properties, Property},
+    scene::Scene,
+    source::{self, MonitoringType, OutputFlags, Source, SourceType, Volume},
+};
 use tonic::{Request, Response, Status};
 
 pub use self::inputs_service_server::InputsServiceServer;
-use crate::precondition;
+use crate::{precondition, precondition_fn};
 
 tonic::include_proto!("inputs.v1");
 
+/// Allowed range for the audio sync offset in milliseconds, same as in the OBS UI.
+const SYNC_OFFSET_RANGE: RangeInclusive<i64> = -950..=20_000;
+
 pub struct InputsService;
 
 #[tonic::async_trait]
@@ -51,7 +62,104 @@ impl inputs_service_server::InputsService for InputsService {
         &self,
         request: Request<ListSpecialRequest>,
     ) -> Result<Response<ListSpecialResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ListSpecialRequest {} = request.into_inner();
+
+        let name = |channel: SpecialChannel| {
+            Source::by_output_channel(channel as u32).map(|source| source.name())
+        };
+
+        Ok(Response::new(ListSpecialResponse {
+            desktop_1: name(SpecialChannel::Desktop1),
+            desktop_2: name(SpecialChannel::Desktop2),
+            mic_1: name(SpecialChannel::Mic1),
+            mic_2: name(SpecialChannel::Mic2),
+            mic_3: name(SpecialChannel::Mic3),
+            mic_4: name(SpecialChannel::Mic4),
+        }))
+    }
+
+    async fn set_special(
+        &self,
+        request: Request<SetSpecialRequest>,
+    ) -> Result<Response<SetSpecialResponse>, Status> {
+        let SetSpecialRequest {
+            channel,
+            device_id,
+            kind,
+        } = request.into_inner();
+
+        let channel = SpecialChannel::try_from(channel)
+            .ok()
+            .filter(|channel| *channel != SpecialChannel::Unspecified)
+            .ok_or_else(|| Status::invalid_argument("invalid channel"))?;
+        // The enum values are the same as the native channel numbers.
+        let index = channel as u32;
+        let current = Source::by_output_channel(index);
+
+        if device_id.is_empty() {
+            if let Some(current) = current {
+                Source::set_output_channel(index, None);
+                current.remove();
+            }
+
+            return Ok(Response::new(SetSpecialResponse { name: None }));
+        }
+
+        let kind = if kind.is_empty() {
+            default_special_kind(channel).to_owned()
+        } else {
+            kind
+        };
+
+        precondition!(
+            source::list_input_types().contains(&kind),
+            "`{kind}` isn't a known input kind"
+        );
+
+        let mut settings = Data::new();
+        settings.set_string("device_id", &device_id);
+
+        // Reuse the current input if it's of the same kind, to keep its filters and other state.
+        // Otherwise, it's replaced with a new one.
+        if let Some(current) = current.as_ref().filter(|current| current.id() == kind) {
+            current.update(settings);
+            return Ok(Response::new(SetSpecialResponse {
+                name: Some(current.name()),
+            }));
+        }
+
+        let name = special_name(channel);
+        // The current input usually carries the special name itself, which isn't a collision as
+        // it's replaced anyway.
+        let renamed = current.as_ref().filter(|current| current.name() == name);
+        precondition!(
+            renamed.is_some() || Source::by_name(name).is_none(),
+            "a source named `{name}` already exists"
+        );
+
+        // Create the replacement before touching the current input, so a failure leaves the
+        // channel as it was. Source names must be unique, so the current input has to give up
+        // its name in the meantime.
+        if let Some(current) = renamed {
+            current.set_name(&format!("{name} (replaced)"));
+        }
+
+        let Some(input) = Source::create_input(&kind, name, Some(&settings)) else {
+            if let Some(current) = renamed {
+                current.set_name(name);
+            }
+            return Err(Status::internal(format!("failed creating input `{name}`")));
+        };
+
+        Source::set_output_channel(index, Some(&input));
+
+        if let Some(current) = current {
+            current.remove();
+        }
+
+        Ok(Response::new(SetSpecialResponse {
+            name: Some(input.name()),
+        }))
     }
 
     async fn default_settings(
@@ -175,42 +283,107 @@ impl inputs_service_server::InputsService for InputsService {
         &self,
         request: Request<AudioSyncOffsetRequest>,
     ) -> Result<Response<AudioSyncOffsetResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let AudioSyncOffsetRequest { name } = request.into_inner();
+        let input = find_audio_input(&name)?;
+
+        Ok(Response::new(AudioSyncOffsetResponse {
+            offset: input.sync_offset().whole_milliseconds() as i64,
+        }))
     }
 
     async fn set_audio_sync_offset(
         &self,
         request: Request<SetAudioSyncOffsetRequest>,
     ) -> Result<Response<SetAudioSyncOffsetResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetAudioSyncOffsetRequest { name, offset } = request.into_inner();
+        precondition!(
+            SYNC_OFFSET_RANGE.contains(&offset),
+            "offset must be in range of {}ms to {}ms",
+            SYNC_OFFSET_RANGE.start(),
+            SYNC_OFFSET_RANGE.end()
+        );
+
+        let mut input = find_audio_input(&name)?;
+
+        input.set_sync_offset(obs::Duration::milliseconds(offset));
+
+        Ok(Response::new(SetAudioSyncOffsetResponse {}))
     }
 
     async fn tracks(
         &self,
         request: Request<TracksRequest>,
     ) -> Result<Response<TracksResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let TracksRequest { name } = request.into_inner();
+        let input = find_audio_input(&name)?;
+
+        Ok(Response::new(TracksResponse {
+            tracks: tracks_to_map(input.audio_mixers()),
+        }))
     }
 
     async fn set_tracks(
         &self,
         request: Request<SetTracksRequest>,
     ) -> Result<Response<SetTracksResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetTracksRequest { name, tracks } = request.into_inner();
+        let mut input = find_audio_input(&name)?;
+
+        let mut mixers = input.audio_mixers();
+
+        for (track, enabled) in tracks {
+            precondition!(
+                (1..=mixers.len() as u32).contains(&track),
+                "track number must be between 1 and {}",
+                mixers.len()
+            );
+
+            mixers[track as usize - 1] = enabled;
+        }
+
+        input.set_audio_mixers(mixers);
+
+        Ok(Response::new(SetTracksResponse {
+            tracks: tracks_to_map(mixers),
+        }))
     }
 
     async fn monitor_mode(
         &self,
         request: Request<MonitorModeRequest>,
     ) -> Result<Response<MonitorModeResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let MonitorModeRequest { name } = request.into_inner();
+        let input = find_audio_input(&name)?;
+
+        let mode = match input.monitoring_type() {
+            MonitoringType::None => MonitorMode::None,
+            MonitoringType::MonitorOnly => MonitorMode::MonitorOnly,
+            MonitoringType::MonitorAndOutput => MonitorMode::MonitorAndOutput,
+        };
+
+        Ok(Response::new(MonitorModeResponse { mode: mode.into() }))
     }
 
     async fn set_monitor_mode(
         &self,
         request: Request<SetMonitorModeRequest>,
     ) -> Result<Response<SetMonitorModeResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetMonitorModeRequest { name, mode } = request.into_inner();
+
+        let mode = match MonitorMode::try_from(mode) {
+            Ok(MonitorMode::None) => MonitoringType::None,
+            Ok(MonitorMode::MonitorOnly) => MonitoringType::MonitorOnly,
+            Ok(MonitorMode::MonitorAndOutput) => MonitoringType::MonitorAndOutput,
+            Ok(MonitorMode::Unspecified) | Err(_) => {
+                return Err(Status::invalid_argument("invalid monitor mode"));
+            }
+        };
+
+        let mut input = find_audio_input(&name)?;
+
+        input.set_monitoring_type(mode);
+
+        Ok(Response::new(SetMonitorModeResponse {}))
     }
 
     async fn is_active(
@@ -224,34 +397,219 @@ impl inputs_service_server::InputsService for InputsService {
         &self,
         request: Request<PropertiesListPropertyItemsRequest>,
     ) -> Result<Response<PropertiesListPropertyItemsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        use self::properties_list_property_items_response::{item::Value, Item};
+
+        let PropertiesListPropertyItemsRequest { name, property } = request.into_inner();
+        let input = find_input(&name)?;
+        let properties = input
+            .properties()
+            .ok_or_else(precondition_fn!("input `{name}` doesn't have properties"))?;
+        let property = find_property(&properties, &property)?;
+        let list = property.as_list().ok_or_else(precondition_fn!(
+            "property `{}` isn't a list",
+            property.name()
+        ))?;
+
+        let format = list.format();
+        let items = (0..list.count())
+            .map(|i| Item {
+                name: list.item_name(i),
+                enabled: !list.item_disabled(i),
+                value: match format {
+                    ComboFormat::Int => Some(Value::Int(list.item_int(i))),
+                    ComboFormat::Float => Some(Value::Float(list.item_float(i))),
+                    ComboFormat::String => Some(Value::String(list.item_string(i))),
+                    ComboFormat::Bool => Some(Value::Bool(list.item_bool(i))),
+                    ComboFormat::Invalid | ComboFormat::Unknown(_) => None,
+                },
+            })
+            .collect();
+
+        Ok(Response::new(PropertiesListPropertyItemsResponse { items }))
     }
 
     async fn press_properties_button(
         &self,
         request: Request<PressPropertiesButtonRequest>,
     ) -> Result<Response<PressPropertiesButtonResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let PressPropertiesButtonRequest { name, property } = request.into_inner();
+        let input = find_input(&name)?;
+        let properties = input
+            .properties()
+            .ok_or_else(precondition_fn!("input `{name}` doesn't have properties"))?;
+        let property = find_property(&properties, &property)?;
+        let button = property.as_button().ok_or_else(precondition_fn!(
+            "property `{}` isn't a button",
+            property.name()
+        ))?;
+
+        precondition!(
+            matches!(button.ty(), ButtonType::Default),
+            "button `{}` only opens a URL and has no action",
+            property.name()
+        );
+
+        button.click(&input);
+
+        Ok(Response::new(PressPropertiesButtonResponse {}))
     }
 
     async fn set_name(
         &self,
         request: Request<SetNameRequest>,
     ) -> Result<Response<SetNameResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetNameRequest { name, new_name } = request.into_inner();
+        precondition!(!new_name.is_empty(), "new name mustn't be empty");
+
+        let input = find_input(&name)?;
+
+        precondition!(
+            Source::by_name(&new_name).is_none(),
+            "a source named `{new_name}` already exists"
+        );
+
+        input.set_name(&new_name);
+
+        Ok(Response::new(SetNameResponse {}))
     }
 
     async fn create(
         &self,
         request: Request<CreateRequest>,
     ) -> Result<Response<CreateResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let CreateRequest {
+            name,
+            kind,
+            settings,
+            scene,
+            enabled,
+        } = request.into_inner();
+        precondition!(!name.is_empty(), "name mustn't be empty");
+        precondition!(!kind.is_empty(), "kind mustn't be empty");
+
+        precondition!(
+            Source::by_name(&name).is_none(),
+            "a source named `{name}` already exists"
+        );
+        precondition!(
+            source::list_input_types().contains(&kind),
+            "`{kind}` isn't a known input kind"
+        );
+
+        let settings = settings
+            .map(|settings| {
+                Data::from_json(&settings)
+                    .map_err(|e| Status::invalid_argument(format!("invalid JSON settings: {e:?}")))
+            })
+            .transpose()?;
+
+        // Look up the scene before creating the input, to not leave an orphaned input behind.
+        let mut scene = if scene.is_empty() {
+            None
+        } else {
+            Some(
+                Source::by_name(&scene)
+                    .and_then(Scene::from_source)
+                    .ok_or_else(precondition_fn!("scene `{scene}` doesn't exist"))?,
+            )
+        };
+
+        let input = Source::create_input(&kind, &name, settings.as_ref())
+            .ok_or_else(|| Status::internal(format!("failed creating input `{name}`")))?;
+
+        let item_id = scene.as_mut().and_then(|scene| {
+            let enabled = enabled.unwrap_or(true);
+
+            graphics::scoped(|| {
+                scene.atomic_update(|scene| {
+                    let item = scene.add(&input)?;
+                    item.set_visible(enabled);
+
+                    Some(item.id())
+                })
+            })
+        });
+
+        Ok(Response::new(CreateResponse { item_id }))
     }
 
     async fn remove(
         &self,
         request: Request<RemoveRequest>,
     ) -> Result<Response<RemoveResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let RemoveRequest { name } = request.into_inner();
+        let input = find_input(&name)?;
+
+        input.remove();
+
+        Ok(Response::new(RemoveResponse {}))
     }
 }
+
+/// Platform specific audio capture kind, that the OBS frontend uses for the given channel.
+fn default_special_kind(channel: SpecialChannel) -> &'static str {
+    let desktop = matches!(channel, SpecialChannel::Desktop1 | SpecialChannel::Desktop2);
+
+    if cfg!(target_os = "windows") {
+        if desktop {
+            "wasapi_output_capture"
+        } else {
+            "wasapi_input_capture"
+        }
+    } else if cfg!(target_os = "macos") {
+        if desktop {
+            "coreaudio_output_capture"
+        } else {
+            "coreaudio_input_capture"
+        }
+    } else if desktop {
+        "pulse_output_capture"
+    } else {
+        "pulse_input_capture"
+    }
+}
+
+/// Name for a newly created special input, following the naming of the OBS frontend.
+fn special_name(channel: SpecialChannel) -> &'static str {
+    match channel {
+        SpecialChannel::Unspecified => "",
+        SpecialChannel::Desktop1 => "Desktop Audio",
+        SpecialChannel::Desktop2 => "Desktop Audio 2",
+        SpecialChannel::Mic1 => "Mic/Aux",
+        SpecialChannel::Mic2 => "Mic/Aux 2",
+        SpecialChannel::Mic3 => "Mic/Aux 3",
+        SpecialChannel::Mic4 => "Mic/Aux 4",
+    }
+}
+
+/// Like [`find_input`], but additionally ensures that the input has audio.
+fn find_audio_input(name: &str) -> Result<Source<'static>, Status> {
+    let input = find_input(name)?;
+
+    precondition!(
+        input.output_flags().contains(OutputFlags::AUDIO),
+        "input `{name}` doesn't have audio"
+    );
+
+    Ok(input)
+}
+
+fn tracks_to_map(mixers: [bool; 6]) -> HashMap<u32, bool> {
+    (1..).zip(mixers).collect()
+}
+
+fn find_property<'a>(properties: &'a Properties<'_>, name: &str) -> Result<Property<'a>, Status> {
+    precondition!(!name.is_empty(), "property name mustn't be empty");
+
+    properties
+        .get(name)
+        .ok_or_else(precondition_fn!("property `{name}` doesn't exist"))
+}
+
+fn find_input(name: &str) -> Result<Source<'static>, Status> {
+    precondition!(!name.is_empty(), "name mustn't be empty");
+
+    Source::by_name(name)
+        .filter(|source| source.ty() == SourceType::Input)
+        .ok_or_else(precondition_fn!("input `{name}` doesn't exist"))
+}
@@ -32,6 +32,7 @@ pub use self::{
     hotkeys::{HotkeysService, HotkeysServiceServer},
     inputs::{InputsService, InputsServiceServer},
     media_inputs::{MediaInputsService, MediaInputsServiceServer},
+    metrics::Metrics,
     outputs::{OutputsService, OutputsServiceServer},
     profiles::{ProfilesService, ProfilesServiceServer},
     projectors::{ProjectorsService, ProjectorsServiceServer},
@@ -53,6 +54,7 @@ mod general;
 mod hotkeys;
 mod inputs;
 mod media_inputs;
+mod metrics;
 mod outputs;
 mod profiles;
 mod projectors;
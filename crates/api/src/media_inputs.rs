@@ -1,63 +1,273 @@
+use std::time::Duration;
+
+use obs::source::{MediaState as ObsMediaState, OutputFlags, Source, SourceType};
+use tokio::{
+    sync::mpsc,
+    time::{self, MissedTickBehavior},
+};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
 pub use self::media_inputs_service_server::MediaInputsServiceServer;
+use crate::{precondition, precondition_fn, util};
 
 tonic::include_proto!("media_inputs.v1");
 
+/// Amount of status reports that are buffered for each watcher.
+const CHANNEL_CAPACITY: usize = 16;
+/// Interval for status reports, if the client doesn't request a specific one.
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_millis(250);
+/// Lower limit for the interval of status reports, to protect against flooding clients.
+const MIN_WATCH_INTERVAL: Duration = Duration::from_millis(10);
+
+impl From<ObsMediaState> for MediaState {
+    fn from(value: ObsMediaState) -> Self {
+        match value {
+            ObsMediaState::None => Self::None,
+            ObsMediaState::Playing => Self::Playing,
+            ObsMediaState::Opening => Self::Opening,
+            ObsMediaState::Buffering => Self::Buffering,
+            ObsMediaState::Paused => Self::Paused,
+            ObsMediaState::Stopped => Self::Stopped,
+            ObsMediaState::Ended => Self::Ended,
+            ObsMediaState::Error => Self::Error,
+            ObsMediaState::Unknown(_) => Self::Unspecified,
+        }
+    }
+}
+
 pub struct MediaInputsService;
 
 #[tonic::async_trait]
 impl media_inputs_service_server::MediaInputsService for MediaInputsService {
+    type WatchStatusStream = ReceiverStream<Result<WatchStatusResponse, Status>>;
+
     async fn status(
         &self,
         request: Request<StatusRequest>,
     ) -> Result<Response<StatusResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StatusRequest { name } = request.into_inner();
+        let input = find_media_input(&name)?;
+
+        Ok(Response::new(StatusResponse {
+            status: Some(media_status(&input)),
+        }))
+    }
+
+    async fn watch_status(
+        &self,
+        request: Request<WatchStatusRequest>,
+    ) -> Result<Response<Self::WatchStatusStream>, Status> {
+        let WatchStatusRequest { name, interval } = request.into_inner();
+
+        let interval = interval
+            .map(Duration::try_from)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("invalid interval: {e:?}")))?
+            .unwrap_or(DEFAULT_WATCH_INTERVAL);
+        precondition!(
+            interval >= MIN_WATCH_INTERVAL,
+            "interval must be at least {}ms",
+            MIN_WATCH_INTERVAL.as_millis()
+        );
+
+        // Fail right away, instead of with the first report, if the input doesn't exist.
+        find_media_input(&name)?;
+
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let mut ticker = time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                ticker.tick().await;
+
+                // The input is looked up again on each tick, to not keep it alive after it was
+                // removed.
+                let response = find_media_input(&name).map(|input| WatchStatusResponse {
+                    status: Some(media_status(&input)),
+                });
+                let removed = response.is_err();
+
+                if tx.send(response).await.is_err() || removed {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
     }
 
     async fn offset_timecode(
         &self,
         request: Request<OffsetTimecodeRequest>,
     ) -> Result<Response<OffsetTimecodeResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let OffsetTimecodeRequest { name, offset } = request.into_inner();
+        let offset = offset
+            .ok_or_else(|| Status::invalid_argument("offset must be specified"))
+            .and_then(|offset| {
+                util::duration_from_proto(offset)
+                    .ok_or_else(|| Status::invalid_argument("invalid offset"))
+            })?;
+
+        let input = find_media_input(&name)?;
+        ensure_loaded(&input)?;
+
+        let cursor = input.media_time().saturating_add(offset).clamp(
+            obs::Duration::ZERO,
+            input.media_duration().max(obs::Duration::ZERO),
+        );
+
+        input.media_set_time(cursor);
+
+        Ok(Response::new(OffsetTimecodeResponse {
+            cursor: Some(util::duration_to_proto(cursor)),
+        }))
     }
 
     async fn set_timecode(
         &self,
         request: Request<SetTimecodeRequest>,
     ) -> Result<Response<SetTimecodeResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetTimecodeRequest { name, cursor } = request.into_inner();
+        let cursor = cursor
+            .ok_or_else(|| Status::invalid_argument("cursor must be specified"))
+            .and_then(|cursor| {
+                util::duration_from_proto(cursor)
+                    .ok_or_else(|| Status::invalid_argument("invalid cursor"))
+            })?;
+
+        let input = find_media_input(&name)?;
+        ensure_loaded(&input)?;
+
+        let duration = input.media_duration();
+        precondition!(
+            (obs::Duration::ZERO..=duration).contains(&cursor),
+            "cursor must be between 0 and the media duration of {}",
+            util::timecode(duration)
+        );
+
+        input.media_set_time(cursor);
+
+        Ok(Response::new(SetTimecodeResponse {}))
     }
 
     async fn is_paused(
         &self,
         request: Request<IsPausedRequest>,
     ) -> Result<Response<IsPausedResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let IsPausedRequest { name } = request.into_inner();
+        let input = find_media_input(&name)?;
+
+        Ok(Response::new(IsPausedResponse {
+            paused: matches!(input.media_state(), ObsMediaState::Paused),
+        }))
     }
 
     async fn stop(&self, request: Request<StopRequest>) -> Result<Response<StopResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StopRequest { name } = request.into_inner();
+        let input = find_media_input(&name)?;
+
+        input.media_stop();
+
+        Ok(Response::new(StopResponse {}))
     }
 
     async fn restart(
         &self,
         request: Request<RestartRequest>,
     ) -> Result<Response<RestartResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let RestartRequest { name } = request.into_inner();
+        let input = find_media_input(&name)?;
+
+        input.media_restart();
+
+        Ok(Response::new(RestartResponse {}))
     }
 
     async fn play_next(
         &self,
         request: Request<PlayNextRequest>,
     ) -> Result<Response<PlayNextResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let PlayNextRequest { name } = request.into_inner();
+        let input = find_media_input(&name)?;
+
+        input.media_next();
+
+        Ok(Response::new(PlayNextResponse {}))
     }
 
     async fn play_previous(
         &self,
         request: Request<PlayPreviousRequest>,
     ) -> Result<Response<PlayPreviousResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let PlayPreviousRequest { name } = request.into_inner();
+        let input = find_media_input(&name)?;
+
+        input.media_previous();
+
+        Ok(Response::new(PlayPreviousResponse {}))
+    }
+}
+
+fn find_media_input(name: &str) -> Result<Source<'static>, Status> {
+    precondition!(!name.is_empty(), "name mustn't be empty");
+
+    let input = Source::by_name(name)
+        .filter(|source| source.ty() == SourceType::Input)
+        .ok_or_else(precondition_fn!("input `{name}` doesn't exist"))?;
+
+    precondition!(
+        input
+            .output_flags()
+            .contains(OutputFlags::CONTROLLABLE_MEDIA),
+        "input `{name}` isn't a media input"
+    );
+
+    Ok(input)
+}
+
+/// Whether the input currently has media loaded, so its duration and cursor are meaningful.
+fn is_loaded(input: &Source<'_>) -> bool {
+    matches!(
+        input.media_state(),
+        ObsMediaState::Playing
+            | ObsMediaState::Opening
+            | ObsMediaState::Buffering
+            | ObsMediaState::Paused
+    )
+}
+
+fn ensure_loaded(input: &Source<'_>) -> Result<(), Status> {
+    precondition!(
+        is_loaded(input),
+        "input `{}` has no media loaded",
+        input.name()
+    );
+
+    Ok(())
+}
+
+fn media_status(input: &Source<'_>) -> MediaStatus {
+    let state = MediaState::from(input.media_state()).into();
+
+    if !is_loaded(input) {
+        return MediaStatus {
+            state,
+            ..MediaStatus::default()
+        };
+    }
+
+    let duration = input.media_duration();
+    let cursor = input.media_time();
+
+    MediaStatus {
+        state,
+        duration: Some(util::duration_to_proto(duration)),
+        cursor: Some(util::duration_to_proto(cursor)),
+        remaining: Some(util::duration_to_proto(
+            (duration - cursor).max(obs::Duration::ZERO),
+        )),
     }
 }
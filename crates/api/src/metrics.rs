@@ -0,0 +1,50 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use tonic::{Request, Status};
+
+/// Counters about the usage of the gRPC server, that are reported as part of the general stats.
+/// It is cheap to clone, so the same instance can be shared between several servers.
+#[derive(Clone, Default)]
+pub struct Metrics(Arc<Counters>);
+
+#[derive(Default)]
+struct Counters {
+    connections: AtomicU64,
+    requests: AtomicU64,
+}
+
+impl Metrics {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a newly accepted client connection.
+    pub fn connection_accepted(&self) {
+        self.0.connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Create an interceptor, that counts every request passing through it.
+    pub fn interceptor(
+        &self,
+    ) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone + Send + Sync + 'static
+    {
+        let metrics = self.clone();
+
+        move |request| {
+            metrics.0.requests.fetch_add(1, Ordering::Relaxed);
+            Ok(request)
+        }
+    }
+
+    pub(crate) fn connections(&self) -> u64 {
+        self.0.connections.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn requests(&self) -> u64 {
+        self.0.requests.load(Ordering::Relaxed)
+    }
+}
@@ -1,53 +1,157 @@
+use obs::data::Data;
 use tonic::{Request, Response, Status};
 
 pub use self::outputs_service_server::OutputsServiceServer;
+use crate::{precondition, precondition_fn};
 
 tonic::include_proto!("outputs.v1");
 
+impl From<obs::output::Flags> for output::Flags {
+    fn from(value: obs::output::Flags) -> Self {
+        Self {
+            raw_value: value.bits(),
+            audio: value.contains(obs::output::Flags::AUDIO),
+            video: value.contains(obs::output::Flags::VIDEO),
+            encoded: value.contains(obs::output::Flags::ENCODED),
+            multi_track: value.contains(obs::output::Flags::MULTI_TRACK),
+            service: value.contains(obs::output::Flags::SERVICE),
+            can_pause: value.contains(obs::output::Flags::CAN_PAUSE),
+        }
+    }
+}
+
+impl From<obs::output::Output<'_>> for Output {
+    fn from(value: obs::output::Output<'_>) -> Self {
+        Self {
+            name: value.name(),
+            kind: value.id(),
+            width: value.width(),
+            height: value.height(),
+            flags: Some(value.flags().into()),
+            active: value.active(),
+        }
+    }
+}
+
 pub struct OutputsService;
 
 #[tonic::async_trait]
 impl outputs_service_server::OutputsService for OutputsService {
     async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ListRequest {} = request.into_inner();
+
+        Ok(Response::new(ListResponse {
+            outputs: obs::output::list_outputs()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }))
     }
 
     async fn status(
         &self,
         request: Request<StatusRequest>,
     ) -> Result<Response<StatusResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StatusRequest { name } = request.into_inner();
+        let output = find_output(&name)?;
+
+        Ok(Response::new(StatusResponse {
+            active: output.active(),
+            reconnecting: output.reconnecting(),
+            bytes: output.total_bytes(),
+            skipped_frames: output.frames_dropped(),
+            total_frames: output.total_frames(),
+            congestion: output.congestion(),
+        }))
     }
 
     async fn toggle(
         &self,
         request: Request<ToggleRequest>,
     ) -> Result<Response<ToggleResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ToggleRequest { name } = request.into_inner();
+        let output = find_output(&name)?;
+
+        let active = output.active();
+
+        if active {
+            output.stop();
+        } else {
+            start_output(&output)?;
+        }
+
+        Ok(Response::new(ToggleResponse { active: !active }))
     }
 
     async fn start(
         &self,
         request: Request<StartRequest>,
     ) -> Result<Response<StartResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StartRequest { name } = request.into_inner();
+        let output = find_output(&name)?;
+
+        precondition!(!output.active(), "output already active");
+
+        start_output(&output)?;
+
+        Ok(Response::new(StartResponse {}))
     }
 
     async fn stop(&self, request: Request<StopRequest>) -> Result<Response<StopResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StopRequest { name, force } = request.into_inner();
+        let output = find_output(&name)?;
+
+        precondition!(output.active(), "output already inactive");
+
+        if force {
+            output.force_stop();
+        } else {
+            output.stop();
+        }
+
+        Ok(Response::new(StopResponse {}))
     }
 
     async fn settings(
         &self,
         request: Request<SettingsRequest>,
     ) -> Result<Response<SettingsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SettingsRequest { name } = request.into_inner();
+        let output = find_output(&name)?;
+
+        Ok(Response::new(SettingsResponse {
+            settings: output.settings().to_json(),
+        }))
     }
 
     async fn set_settings(
         &self,
         request: Request<SetSettingsRequest>,
     ) -> Result<Response<SetSettingsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetSettingsRequest { name, settings } = request.into_inner();
+        let output = find_output(&name)?;
+        let settings = Data::from_json(&settings)
+            .map_err(|e| Status::invalid_argument(format!("invalid JSON settings: {e:?}")))?;
+
+        output.update(settings);
+
+        Ok(Response::new(SetSettingsResponse {}))
+    }
+}
+
+fn find_output(name: &str) -> Result<obs::output::Output<'static>, Status> {
+    precondition!(!name.is_empty(), "output name mustn't be empty");
+
+    obs::output::Output::by_name(name).ok_or_else(precondition_fn!("`{name}` doesn't exist"))
+}
+
+fn start_output(output: &obs::output::Output<'_>) -> Result<(), Status> {
+    if output.start() {
+        Ok(())
+    } else {
+        Err(Status::internal(format!(
+            "failed starting `{}`",
+            output.name()
+        )))
     }
 }
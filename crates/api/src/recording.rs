@@ -1,6 +1,13 @@
+use obs::{
+    callback::calldata::Calldata,
+    frontend::{self, recording},
+    output::Output,
+};
+use tokio::fs;
 use tonic::{Request, Response, Status};
 
 pub use self::recording_service_server::RecordingServiceServer;
+use crate::{precondition, precondition_fn, util};
 
 tonic::include_proto!("recording.v1");
 
@@ -12,73 +19,254 @@ impl recording_service_server::RecordingService for RecordingService {
         &self,
         request: Request<StatusRequest>,
     ) -> Result<Response<StatusResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StatusRequest {} = request.into_inner();
+
+        let Some(output) = recording::output() else {
+            return Ok(Response::new(StatusResponse {
+                timecode: util::timecode(obs::Duration::ZERO),
+                duration: Some(util::duration_to_proto(obs::Duration::ZERO)),
+                ..StatusResponse::default()
+            }));
+        };
+
+        let active = output.active();
+        let duration = util::output_duration(&output);
+
+        Ok(Response::new(StatusResponse {
+            active,
+            paused: recording::paused(),
+            timecode: util::timecode(duration),
+            duration: Some(util::duration_to_proto(duration)),
+            bytes: output.total_bytes(),
+            output_path: if active {
+                output_path(&output)
+            } else {
+                String::new()
+            },
+        }))
     }
 
     async fn toggle(
         &self,
         request: Request<ToggleRequest>,
     ) -> Result<Response<ToggleResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ToggleRequest {} = request.into_inner();
+
+        let active = recording::active();
+
+        if active {
+            recording::stop();
+        } else {
+            recording::start();
+        }
+
+        Ok(Response::new(ToggleResponse { active: !active }))
     }
 
     async fn start(
         &self,
         request: Request<StartRequest>,
     ) -> Result<Response<StartResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StartRequest {} = request.into_inner();
+
+        precondition!(!recording::active(), "recording already active");
+
+        recording::start();
+
+        Ok(Response::new(StartResponse {}))
     }
 
     async fn stop(&self, request: Request<StopRequest>) -> Result<Response<StopResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StopRequest {} = request.into_inner();
+
+        precondition!(recording::active(), "recording already inactive");
+
+        let output_path = recording::output()
+            .map(|output| output_path(&output))
+            .unwrap_or_default();
+
+        recording::stop();
+
+        Ok(Response::new(StopResponse { output_path }))
     }
 
     async fn toggle_pause(
         &self,
         request: Request<TogglePauseRequest>,
     ) -> Result<Response<TogglePauseResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let TogglePauseRequest {} = request.into_inner();
+
+        ensure_pausable()?;
+
+        let paused = recording::paused();
+        recording::pause(!paused);
+
+        Ok(Response::new(TogglePauseResponse { paused: !paused }))
     }
 
     async fn pause(
         &self,
         request: Request<PauseRequest>,
     ) -> Result<Response<PauseResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let PauseRequest {} = request.into_inner();
+
+        ensure_pausable()?;
+        precondition!(!recording::paused(), "recording already paused");
+
+        recording::pause(true);
+
+        Ok(Response::new(PauseResponse {}))
     }
 
     async fn resume(
         &self,
         request: Request<ResumeRequest>,
     ) -> Result<Response<ResumeResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ResumeRequest {} = request.into_inner();
+
+        ensure_pausable()?;
+        precondition!(recording::paused(), "recording not paused");
+
+        recording::pause(false);
+
+        Ok(Response::new(ResumeResponse {}))
     }
 
     async fn directory(
         &self,
         request: Request<DirectoryRequest>,
     ) -> Result<Response<DirectoryResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let DirectoryRequest {} = request.into_inner();
+
+        let directory = util::recording_directory(&frontend::profile_config()).unwrap_or_default();
+
+        Ok(Response::new(DirectoryResponse { directory }))
     }
 
     async fn set_directory(
         &self,
         request: Request<SetDirectoryRequest>,
     ) -> Result<Response<SetDirectoryResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetDirectoryRequest { directory } = request.into_inner();
+
+        precondition!(!directory.is_empty(), "directory mustn't be empty");
+
+        fs::create_dir_all(&directory).await.map_err(|e| {
+            Status::invalid_argument(format!("failed creating recording directory: {e:?}"))
+        })?;
+
+        let profile = frontend::profile_config();
+        profile.set_string("AdvOut", "RecFilePath", &directory);
+        profile.set_string("AdvOut", "FFFilePath", &directory);
+        profile.set_string("SimpleOutput", "FilePath", &directory);
+        profile
+            .save_safe("tmp", None)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(SetDirectoryResponse {}))
     }
 
     async fn filename_formatting(
         &self,
         request: Request<FilenameFormattingRequest>,
     ) -> Result<Response<FilenameFormattingResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let FilenameFormattingRequest {} = request.into_inner();
+
+        let profile = frontend::profile_config();
+        let formatting = profile
+            .string("Output", "FilenameFormatting")
+            .or_else(|| profile.default_string("Output", "FilenameFormatting"))
+            .unwrap_or_default();
+
+        Ok(Response::new(FilenameFormattingResponse { formatting }))
     }
 
     async fn set_filename_formatting(
         &self,
         request: Request<SetFilenameFormattingRequest>,
     ) -> Result<Response<SetFilenameFormattingResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetFilenameFormattingRequest { formatting } = request.into_inner();
+
+        precondition!(!formatting.is_empty(), "formatting mustn't be empty");
+
+        let profile = frontend::profile_config();
+        profile.set_string("Output", "FilenameFormatting", &formatting);
+        profile
+            .save_safe("tmp", None)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(SetFilenameFormattingResponse {}))
+    }
+
+    async fn split_file(
+        &self,
+        request: Request<SplitFileRequest>,
+    ) -> Result<Response<SplitFileResponse>, Status> {
+        let SplitFileRequest {} = request.into_inner();
+
+        let output = active_output()?;
+        let mut handler = output.proc_handler();
+        let mut calldata = Calldata::default();
+
+        precondition!(
+            handler.call("split_file", &mut calldata),
+            "recording output doesn't support splitting files"
+        );
+        precondition!(
+            calldata.bool("split_file_enabled").unwrap_or_default(),
+            "file splitting isn't enabled in the recording settings"
+        );
+
+        Ok(Response::new(SplitFileResponse {}))
     }
+
+    async fn add_chapter(
+        &self,
+        request: Request<AddChapterRequest>,
+    ) -> Result<Response<AddChapterResponse>, Status> {
+        let AddChapterRequest { name } = request.into_inner();
+
+        let output = active_output()?;
+        let mut handler = output.proc_handler();
+        let mut calldata = Calldata::default();
+
+        if !name.is_empty() {
+            calldata.set_string("chapter_name", &name);
+        }
+
+        precondition!(
+            handler.call("add_chapter", &mut calldata),
+            "recording output doesn't support chapters (requires Hybrid MP4 and OBS 30.2+)"
+        );
+
+        Ok(Response::new(AddChapterResponse {}))
+    }
+}
+
+/// Get the recording output, but only if it's currently active.
+fn active_output() -> Result<Output<'static>, Status> {
+    recording::output()
+        .filter(|output| output.active())
+        .ok_or_else(precondition_fn!("recording inactive"))
+}
+
+/// Make sure the recording is active and its output supports pausing.
+fn ensure_pausable() -> Result<(), Status> {
+    let output = active_output()?;
+
+    precondition!(output.can_pause(), "recording can't be paused");
+
+    Ok(())
+}
+
+/// File path of the recording output, which is stored under different keys, depending on the
+/// output type.
+fn output_path(output: &Output<'_>) -> String {
+    let settings = output.settings();
+
+    settings
+        .item_by_name("url")
+        .or_else(|| settings.item_by_name("path"))
+        .and_then(|item| item.string())
+        .unwrap_or_default()
 }
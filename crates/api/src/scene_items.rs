@@ -1,105 +1,787 @@
+use obs::{
+    graphics,
+    scene::{BlendingType, EditableSceneItem, Scene},
+    source::{Source, SourceType},
+};
 use tonic::{Request, Response, Status};
 
 pub use self::scene_items_service_server::SceneItemsServiceServer;
+use crate::{precondition, precondition_fn};
 
 tonic::include_proto!("scene_items.v1");
 
+/// Private settings key, under which the OBS frontend stores the color preset of an item.
+const COLOR_PRESET_KEY: &str = "color-preset";
+/// Private settings key, under which the OBS frontend stores the custom color of an item.
+const COLOR_KEY: &str = "color";
+
+impl From<BlendingType> for BlendMode {
+    fn from(value: BlendingType) -> Self {
+        match value {
+            BlendingType::Normal => Self::Normal,
+            BlendingType::Additive => Self::Additive,
+            BlendingType::Subtract => Self::Subtract,
+            BlendingType::Screen => Self::Screen,
+            BlendingType::Multiply => Self::Multiply,
+            BlendingType::Lighten => Self::Lighten,
+            BlendingType::Darken => Self::Darken,
+            BlendingType::Unknown(_) => Self::Unspecified,
+        }
+    }
+}
+
+impl From<obs::scene::Alignment> for Alignment {
+    fn from(value: obs::scene::Alignment) -> Self {
+        use obs::scene::Alignment as A;
+
+        match (
+            value.contains(A::LEFT),
+            value.contains(A::RIGHT),
+            value.contains(A::TOP),
+            value.contains(A::BOTTOM),
+        ) {
+            (true, _, true, _) => Self::TopLeft,
+            (_, true, true, _) => Self::TopRight,
+            (true, _, _, true) => Self::BottomLeft,
+            (_, true, _, true) => Self::BottomRight,
+            (true, ..) => Self::Left,
+            (_, true, ..) => Self::Right,
+            (_, _, true, _) => Self::Top,
+            (.., true) => Self::Bottom,
+            _ => Self::Center,
+        }
+    }
+}
+
+impl Alignment {
+    fn to_native(self) -> Option<obs::scene::Alignment> {
+        use obs::scene::Alignment as A;
+
+        Some(match self {
+            Self::Unspecified => return None,
+            Self::Center => A::CENTER,
+            Self::Left => A::LEFT,
+            Self::Right => A::RIGHT,
+            Self::Top => A::TOP,
+            Self::Bottom => A::BOTTOM,
+            Self::TopLeft => A::TOP | A::LEFT,
+            Self::TopRight => A::TOP | A::RIGHT,
+            Self::BottomLeft => A::BOTTOM | A::LEFT,
+            Self::BottomRight => A::BOTTOM | A::RIGHT,
+        })
+    }
+}
+
+impl From<obs::scene::BoundsType> for BoundsType {
+    fn from(value: obs::scene::BoundsType) -> Self {
+        use obs::scene::BoundsType as B;
+
+        match value {
+            B::None => Self::None,
+            B::Stretch => Self::Stretch,
+            B::ScaleInner => Self::ScaleInner,
+            B::ScaleOuter => Self::ScaleOuter,
+            B::ScaleToWidth => Self::ScaleToWidth,
+            B::ScaleToHeight => Self::ScaleToHeight,
+            B::MaxOnly => Self::MaxOnly,
+            B::Unknown(_) => Self::Unspecified,
+        }
+    }
+}
+
+impl BoundsType {
+    fn to_native(self) -> Option<obs::scene::BoundsType> {
+        use obs::scene::BoundsType as B;
+
+        Some(match self {
+            Self::Unspecified => return None,
+            Self::None => B::None,
+            Self::Stretch => B::Stretch,
+            Self::ScaleInner => B::ScaleInner,
+            Self::ScaleOuter => B::ScaleOuter,
+            Self::ScaleToWidth => B::ScaleToWidth,
+            Self::ScaleToHeight => B::ScaleToHeight,
+            Self::MaxOnly => B::MaxOnly,
+        })
+    }
+}
+
+impl From<&obs::scene::SceneItem<'_>> for Transform {
+    fn from(item: &obs::scene::SceneItem<'_>) -> Self {
+        let source = item.source();
+        let (source_width, source_height) = (source.width(), source.height());
+        let (pos_x, pos_y) = item.pos();
+        let (scale_x, scale_y) = item.scale();
+        let (bounds_x, bounds_y) = item.bounds();
+        let (left, top, right, bottom) = item.crop();
+        let bounds_type = item.bounds_type();
+
+        let (width, height) = if matches!(bounds_type, obs::scene::BoundsType::None) {
+            (
+                (source_width as i32 - left - right).max(0) as f32 * scale_x,
+                (source_height as i32 - top - bottom).max(0) as f32 * scale_y,
+            )
+        } else {
+            (bounds_x, bounds_y)
+        };
+
+        Self {
+            position: Some(Vec2 { x: pos_x, y: pos_y }),
+            rotation: item.rot(),
+            scale: Some(Vec2 {
+                x: scale_x,
+                y: scale_y,
+            }),
+            alignment: Alignment::from(item.alignment()).into(),
+            bounds_type: BoundsType::from(bounds_type).into(),
+            bounds_alignment: Alignment::from(item.bounds_alignment()).into(),
+            bounds: Some(Vec2 {
+                x: bounds_x,
+                y: bounds_y,
+            }),
+            crop: Some(Crop {
+                left,
+                top,
+                right,
+                bottom,
+            }),
+            source_width,
+            source_height,
+            width,
+            height,
+        }
+    }
+}
+
+/// Validated form of a [`TransformUpdate`], that can be applied to a scene item.
+struct TransformChanges {
+    position: Option<(f32, f32)>,
+    rotation: Option<f32>,
+    scale: Option<(f32, f32)>,
+    alignment: Option<obs::scene::Alignment>,
+    bounds_type: Option<obs::scene::BoundsType>,
+    bounds_alignment: Option<obs::scene::Alignment>,
+    bounds: Option<(f32, f32)>,
+    crop: Option<(i32, i32, i32, i32)>,
+}
+
+impl TryFrom<TransformUpdate> for TransformChanges {
+    type Error = Status;
+
+    fn try_from(value: TransformUpdate) -> Result<Self, Self::Error> {
+        let TransformUpdate {
+            position,
+            rotation,
+            scale,
+            alignment,
+            bounds_type,
+            bounds_alignment,
+            bounds,
+            crop,
+        } = value;
+
+        let position = position.map(|Vec2 { x, y }| (x, y));
+        if let Some((x, y)) = position {
+            precondition!(x.is_finite() && y.is_finite(), "position must be finite");
+        }
+        if let Some(rotation) = rotation {
+            precondition!(rotation.is_finite(), "rotation must be finite");
+        }
+        let scale = scale.map(|Vec2 { x, y }| (x, y));
+        if let Some((x, y)) = scale {
+            precondition!(
+                x.is_normal() && y.is_normal(),
+                "scale must be finite and non-zero"
+            );
+        }
+        let bounds = bounds.map(|Vec2 { x, y }| (x, y));
+        if let Some((x, y)) = bounds {
+            precondition!(
+                x.is_finite() && y.is_finite() && x >= 0.0 && y >= 0.0,
+                "bounds must be finite and non-negative"
+            );
+        }
+        let crop = crop.map(
+            |Crop {
+                 left,
+                 top,
+                 right,
+                 bottom,
+             }| (left, top, right, bottom),
+        );
+        if let Some((left, top, right, bottom)) = crop {
+            precondition!(
+                left >= 0 && top >= 0 && right >= 0 && bottom >= 0,
+                "crop mustn't be negative"
+            );
+        }
+
+        Ok(Self {
+            position,
+            rotation,
+            scale,
+            alignment: Alignment::try_from(alignment)
+                .map_err(|_| Status::invalid_argument("invalid alignment"))?
+                .to_native(),
+            bounds_type: BoundsType::try_from(bounds_type)
+                .map_err(|_| Status::invalid_argument("invalid bounds type"))?
+                .to_native(),
+            bounds_alignment: Alignment::try_from(bounds_alignment)
+                .map_err(|_| Status::invalid_argument("invalid bounds alignment"))?
+                .to_native(),
+            bounds,
+            crop,
+        })
+    }
+}
+
+impl TransformChanges {
+    fn apply(&self, item: &mut EditableSceneItem<'_, '_>) {
+        if let Some(position) = self.position {
+            item.set_pos(position);
+        }
+        if let Some(rotation) = self.rotation {
+            item.set_rot(rotation);
+        }
+        if let Some(scale) = self.scale {
+            item.set_scale(scale);
+        }
+        if let Some(alignment) = self.alignment {
+            item.set_alignment(alignment);
+        }
+        if let Some(bounds_type) = self.bounds_type {
+            item.set_bounds_type(bounds_type);
+        }
+        if let Some(alignment) = self.bounds_alignment {
+            item.set_bounds_alignment(alignment);
+        }
+        if let Some(bounds) = self.bounds {
+            item.set_bounds(bounds);
+        }
+        if let Some(crop) = self.crop {
+            item.set_crop(crop);
+        }
+    }
+}
+
 pub struct SceneItemsService;
 
 #[tonic::async_trait]
 impl scene_items_service_server::SceneItemsService for SceneItemsService {
     async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ListRequest { scene, recursive } = request.into_inner();
+        let scene = find_scene(&scene)?;
+
+        let mut items = Vec::new();
+        collect_items(scene.list_items(), "", recursive, &mut items);
+
+        Ok(Response::new(ListResponse { items }))
     }
 
     async fn list_group(
         &self,
         request: Request<ListGroupRequest>,
     ) -> Result<Response<ListGroupResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ListGroupRequest { group, recursive } = request.into_inner();
+        let scene = find_group(&group)?;
+
+        let mut items = Vec::new();
+        collect_items(scene.list_items(), "", recursive, &mut items);
+
+        Ok(Response::new(ListGroupResponse { items }))
     }
 
     async fn transform(
         &self,
         request: Request<TransformRequest>,
     ) -> Result<Response<TransformResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let TransformRequest { scene, id } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        Ok(Response::new(TransformResponse {
+            transform: Some((&item).into()),
+        }))
     }
 
     async fn set_transform(
         &self,
         request: Request<SetTransformRequest>,
     ) -> Result<Response<SetTransformResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetTransformRequest {
+            scene,
+            id,
+            transform,
+        } = request.into_inner();
+        let changes = TransformChanges::try_from(transform.unwrap_or_default())?;
+
+        let scene = find_parent(&scene)?;
+        let mut item = find_item(&scene, id)?;
+
+        // Apply all changes within a single deferred update, so only one transform change is
+        // signaled, instead of one per value.
+        item.update(|item| changes.apply(item));
+
+        Ok(Response::new(SetTransformResponse {
+            transform: Some((&item).into()),
+        }))
     }
 
     async fn is_enabled(
         &self,
         request: Request<IsEnabledRequest>,
     ) -> Result<Response<IsEnabledResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let IsEnabledRequest { scene, id } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        Ok(Response::new(IsEnabledResponse {
+            enabled: item.visible(),
+        }))
     }
 
     async fn set_enabled(
         &self,
         request: Request<SetEnabledRequest>,
     ) -> Result<Response<SetEnabledResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetEnabledRequest { scene, id, enabled } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        item.set_visible(enabled);
+
+        Ok(Response::new(SetEnabledResponse {}))
     }
 
     async fn is_locked(
         &self,
         request: Request<IsLockedRequest>,
     ) -> Result<Response<IsLockedResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let IsLockedRequest { scene, id } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        Ok(Response::new(IsLockedResponse {
+            locked: item.locked(),
+        }))
     }
 
     async fn set_locked(
         &self,
         request: Request<SetLockedRequest>,
     ) -> Result<Response<SetLockedResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetLockedRequest { scene, id, locked } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        item.set_locked(locked);
+
+        Ok(Response::new(SetLockedResponse {}))
     }
 
     async fn color(
         &self,
         request: Request<ColorRequest>,
     ) -> Result<Response<ColorResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ColorRequest { scene, id } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        let settings = item.private_settings();
+        let preset = settings.int(COLOR_PRESET_KEY).unwrap_or_default();
+
+        // The native presets are offset by one from the message's ones, which reserve 0 for the
+        // unspecified value.
+        let color = match preset {
+            1 => Color {
+                preset: ColorPreset::Custom.into(),
+                custom: settings.string(COLOR_KEY).unwrap_or_default(),
+            },
+            0 | 2..=9 => Color {
+                preset: preset as i32 + 1,
+                custom: String::new(),
+            },
+            _ => Color::default(),
+        };
+
+        Ok(Response::new(ColorResponse { color: Some(color) }))
     }
 
     async fn set_color(
         &self,
         request: Request<SetColorRequest>,
     ) -> Result<Response<SetColorResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetColorRequest { scene, id, color } = request.into_inner();
+        let Color { preset, custom } =
+            color.ok_or_else(|| Status::invalid_argument("color must be specified"))?;
+
+        let preset = ColorPreset::try_from(preset)
+            .map_err(|_| Status::invalid_argument("invalid color preset"))?;
+        precondition!(
+            preset != ColorPreset::Unspecified,
+            "color preset must be specified"
+        );
+
+        let custom = if preset == ColorPreset::Custom {
+            parse_color(&custom).ok_or_else(precondition_fn!(
+                "custom color must be in the form `#AARRGGBB` or `#RRGGBB`"
+            ))?
+        } else {
+            String::new()
+        };
+
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        let mut settings = item.private_settings();
+        settings.set_int(COLOR_PRESET_KEY, i64::from(preset as i32 - 1));
+        settings.set_string(COLOR_KEY, &custom);
+
+        Ok(Response::new(SetColorResponse {}))
     }
 
     async fn set_index(
         &self,
         request: Request<SetIndexRequest>,
     ) -> Result<Response<SetIndexResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetIndexRequest { scene, id, index } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        let count = scene.list_items().len();
+        precondition!(
+            (index as usize) < count,
+            "index must be less than the item count of {count}"
+        );
+
+        item.set_order_position(index as i32);
+
+        Ok(Response::new(SetIndexResponse {}))
     }
 
     async fn create(
         &self,
         request: Request<CreateRequest>,
     ) -> Result<Response<CreateResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let CreateRequest {
+            scene: scene_name,
+            source: name,
+            enabled,
+            transform,
+        } = request.into_inner();
+        let changes = TransformChanges::try_from(transform.unwrap_or_default())?;
+        let enabled = enabled.unwrap_or(true);
+
+        let mut scene = find_scene(&scene_name)?;
+
+        precondition!(!name.is_empty(), "source name mustn't be empty");
+        let source =
+            Source::by_name(&name).ok_or_else(precondition_fn!("`{name}` doesn't exist"))?;
+        precondition!(
+            matches!(source.ty(), SourceType::Input | SourceType::Scene),
+            "`{name}` is neither an input nor a scene"
+        );
+        ensure_single_group(&scene, &source)?;
+
+        let id = graphics::scoped(|| {
+            scene.atomic_update(|scene| {
+                let mut item = scene.add(&source)?;
+                item.set_visible(enabled);
+                item.update(|item| changes.apply(item));
+
+                Some(item.id())
+            })
+        })
+        .ok_or_else(precondition_fn!(
+            "`{name}` can't be added to `{scene_name}`, as it would create a recursion"
+        ))?;
+
+        Ok(Response::new(CreateResponse { id }))
     }
 
     async fn remove(
         &self,
         request: Request<RemoveRequest>,
     ) -> Result<Response<RemoveResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let RemoveRequest { scene, id } = request.into_inner();
+        let scene = find_parent(&scene)?;
+        let item = find_item(&scene, id)?;
+
+        item.remove();
+
+        Ok(Response::new(RemoveResponse {}))
     }
 
     async fn duplicate(
         &self,
         request: Request<DuplicateRequest>,
     ) -> Result<Response<DuplicateResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let DuplicateRequest {
+            scene: scene_name,
+            id,
+            destination,
+        } = request.into_inner();
+        let scene = find_parent(&scene_name)?;
+        let item = find_item(&scene, id)?;
+
+        let (mut destination, destination_name) = if destination.is_empty() {
+            (find_parent(&scene_name)?, scene_name)
+        } else {
+            (find_scene(&destination)?, destination)
+        };
+
+        // Copies of group items reference the same group source, so they share its children with
+        // the original. Groups can only be shown once per scene, though.
+        let source = item.source();
+        ensure_single_group(&destination, &source)?;
+
+        let id = graphics::scoped(|| {
+            destination.atomic_update(|scene| {
+                let mut copy = scene.add(&source)?;
+                copy.set_visible(item.visible());
+                copy.set_locked(item.locked());
+                copy.update(|copy| copy_transform(&item, copy));
+
+                Some(copy.id())
+            })
+        })
+        .ok_or_else(precondition_fn!(
+            "`{}` can't be added to `{destination_name}`, as it would create a recursion",
+            source.name()
+        ))?;
+
+        Ok(Response::new(DuplicateResponse { id }))
+    }
+}
+
+/// Groups can only be shown once in each scene, so make sure the scene doesn't contain the source
+/// yet, in case it's a group.
+fn ensure_single_group(scene: &Scene<'_>, source: &Source<'_>) -> Result<(), Status> {
+    if !source.is_group() {
+        return Ok(());
+    }
+
+    let name = source.name();
+    precondition!(
+        !scene
+            .list_items()
+            .iter()
+            .any(|item| item.source().name() == name),
+        "group `{name}` already exists in the scene"
+    );
+
+    Ok(())
+}
+
+/// Copy all transform, crop and blending settings from one item to another.
+fn copy_transform(from: &obs::scene::SceneItem<'_>, to: &mut EditableSceneItem<'_, '_>) {
+    to.set_pos(from.pos());
+    to.set_rot(from.rot());
+    to.set_scale(from.scale());
+    to.set_alignment(from.alignment());
+    to.set_bounds_type(from.bounds_type());
+    to.set_bounds_alignment(from.bounds_alignment());
+    to.set_bounds(from.bounds());
+    to.set_crop(from.crop());
+    to.set_scale_filter(from.scale_filter());
+    to.set_blending_method(from.blending_method());
+    to.set_blending_mode(from.blending_mode());
+}
+
+fn find_scene(name: &str) -> Result<Scene<'static>, Status> {
+    precondition!(!name.is_empty(), "scene name mustn't be empty");
+
+    Source::by_name(name)
+        .and_then(Scene::from_source)
+        .ok_or_else(precondition_fn!("scene `{name}` doesn't exist"))
+}
+
+/// Validate a custom color and bring it into the `#aarrggbb` form, as used by the OBS frontend.
+fn parse_color(color: &str) -> Option<String> {
+    let hex = color.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        6 => Some(format!("#ff{}", hex.to_ascii_lowercase())),
+        8 => Some(format!("#{}", hex.to_ascii_lowercase())),
+        _ => None,
+    }
+}
+
+/// Find the scene or group that contains an item, as items can be addressed in either.
+fn find_parent(name: &str) -> Result<Scene<'static>, Status> {
+    precondition!(!name.is_empty(), "scene name mustn't be empty");
+
+    Source::by_name(name)
+        .and_then(Scene::from_group_or_scene)
+        .ok_or_else(precondition_fn!("scene or group `{name}` doesn't exist"))
+}
+
+fn find_item<'a>(scene: &'a Scene<'_>, id: i64) -> Result<obs::scene::SceneItem<'a>, Status> {
+    scene
+        .find_item(id)
+        .ok_or_else(precondition_fn!("item `{id}` doesn't exist"))
+}
+
+fn find_group(name: &str) -> Result<Scene<'static>, Status> {
+    precondition!(!name.is_empty(), "group name mustn't be empty");
+
+    Source::by_name(name)
+        .filter(Source::is_group)
+        .and_then(Scene::from_group_or_scene)
+        .ok_or_else(precondition_fn!("group `{name}` doesn't exist"))
+}
+
+/// Convert the given items into their message form, descending into groups if `recursive` is set.
+/// The items of a group are placed right after the group item itself.
+fn collect_items(
+    items: Vec<obs::scene::SceneItem<'_>>,
+    group: &str,
+    recursive: bool,
+    list: &mut Vec<SceneItem>,
+) {
+    for (index, item) in items.into_iter().enumerate() {
+        let source = item.source();
+        let name = source.name();
+
+        list.push(SceneItem {
+            id: item.id(),
+            index: index as u32,
+            source: name.clone(),
+            kind: source.id(),
+            enabled: item.visible(),
+            locked: item.locked(),
+            is_group: item.is_group(),
+            blend_mode: BlendMode::from(item.blending_mode()).into(),
+            group: group.to_owned(),
+        });
+
+        if let Some(children) = recursive.then(|| item.list_group_items()).flatten() {
+            collect_items(children, &name, recursive, list);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    fn error_code(update: TransformUpdate) -> Option<Code> {
+        TransformChanges::try_from(update)
+            .err()
+            .map(|status| status.code())
+    }
+
+    #[test]
+    fn transform_changes_valid() {
+        let changes = TransformChanges::try_from(TransformUpdate {
+            position: Some(Vec2 { x: -10.0, y: 20.0 }),
+            rotation: Some(-90.0),
+            scale: Some(Vec2 { x: 0.5, y: -2.0 }),
+            alignment: Alignment::TopLeft.into(),
+            bounds_type: BoundsType::ScaleInner.into(),
+            bounds_alignment: Alignment::Unspecified.into(),
+            bounds: Some(Vec2 { x: 0.0, y: 1080.0 }),
+            crop: Some(Crop {
+                left: 0,
+                top: 1,
+                right: 2,
+                bottom: 3,
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(Some((-10.0, 20.0)), changes.position);
+        assert_eq!(Some(-90.0), changes.rotation);
+        assert_eq!(Some((0.5, -2.0)), changes.scale);
+        assert_eq!(
+            Some(obs::scene::Alignment::TOP | obs::scene::Alignment::LEFT),
+            changes.alignment
+        );
+        assert!(matches!(
+            changes.bounds_type,
+            Some(obs::scene::BoundsType::ScaleInner)
+        ));
+        assert_eq!(None, changes.bounds_alignment);
+        assert_eq!(Some((0.0, 1080.0)), changes.bounds);
+        assert_eq!(Some((0, 1, 2, 3)), changes.crop);
+    }
+
+    #[test]
+    fn transform_changes_empty() {
+        let changes = TransformChanges::try_from(TransformUpdate::default()).unwrap();
+
+        assert!(changes.position.is_none());
+        assert!(changes.rotation.is_none());
+        assert!(changes.scale.is_none());
+        assert!(changes.alignment.is_none());
+        assert!(changes.bounds_type.is_none());
+        assert!(changes.bounds_alignment.is_none());
+        assert!(changes.bounds.is_none());
+        assert!(changes.crop.is_none());
+    }
+
+    #[test]
+    fn transform_changes_invalid_values() {
+        let updates = [
+            TransformUpdate {
+                position: Some(Vec2 {
+                    x: f32::NAN,
+                    y: 0.0,
+                }),
+                ..TransformUpdate::default()
+            },
+            TransformUpdate {
+                rotation: Some(f32::INFINITY),
+                ..TransformUpdate::default()
+            },
+            TransformUpdate {
+                scale: Some(Vec2 { x: 1.0, y: 0.0 }),
+                ..TransformUpdate::default()
+            },
+            TransformUpdate {
+                bounds: Some(Vec2 { x: -1.0, y: 1.0 }),
+                ..TransformUpdate::default()
+            },
+            TransformUpdate {
+                crop: Some(Crop {
+                    left: 0,
+                    top: 0,
+                    right: -1,
+                    bottom: 0,
+                }),
+                ..TransformUpdate::default()
+            },
+        ];
+
+        for update in updates {
+            assert_eq!(Some(Code::FailedPrecondition), error_code(update));
+        }
+    }
+
+    #[test]
+    fn transform_changes_invalid_enums() {
+        let updates = [
+            TransformUpdate {
+                alignment: 100,
+                ..TransformUpdate::default()
+            },
+            TransformUpdate {
+                bounds_type: -1,
+                ..TransformUpdate::default()
+            },
+            TransformUpdate {
+                bounds_alignment: 100,
+                ..TransformUpdate::default()
+            },
+        ];
+
+        for update in updates {
+            assert_eq!(Some(Code::InvalidArgument), error_code(update));
+        }
     }
 }
@@ -1,9 +1,26 @@
-use tonic::{Request, Response, Status};
+use obs::{
+    config::Config,
+    data::Data,
+    frontend::{self, profiles, streaming},
+    hotkeys,
+    service::Service,
+};
+use tonic::{Request, Response, Status, Streaming};
 
 pub use self::streaming_service_server::StreamingServiceServer;
+use crate::{precondition, precondition_fn, util};
 
 tonic::include_proto!("streaming.v1");
 
+/// Name for stream services that are newly created, when changing to a different service type.
+const CUSTOM_SERVICE_NAME: &str = "obs_remote_custom_service";
+/// File in the profile directory, that holds the stream encoder settings of the advanced output
+/// mode.
+const STREAM_ENCODER_FILE: &str = "streamEncoder.json";
+/// Maximum length of a single caption, which is the amount of characters that fit on the screen
+/// with CEA-608 captions (15 rows with 32 columns each).
+const MAX_CAPTION_LENGTH: usize = 15 * 32;
+
 pub struct StreamingService;
 
 #[tonic::async_trait]
@@ -12,59 +29,301 @@ impl streaming_service_server::StreamingService for StreamingService {
         &self,
         request: Request<StatusRequest>,
     ) -> Result<Response<StatusResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StatusRequest {} = request.into_inner();
+
+        // The output is only created once streaming was started for the first time.
+        let Some(output) = streaming::output() else {
+            return Ok(Response::new(StatusResponse {
+                timecode: util::timecode(obs::Duration::ZERO),
+                duration: Some(util::duration_to_proto(obs::Duration::ZERO)),
+                ..StatusResponse::default()
+            }));
+        };
+
+        let duration = util::output_duration(&output);
+
+        Ok(Response::new(StatusResponse {
+            active: output.active(),
+            reconnecting: output.reconnecting(),
+            timecode: util::timecode(duration),
+            duration: Some(util::duration_to_proto(duration)),
+            bytes: output.total_bytes(),
+            skipped_frames: output.frames_dropped(),
+            total_frames: output.total_frames(),
+            congestion: output.congestion(),
+        }))
     }
 
     async fn toggle(
         &self,
         request: Request<ToggleRequest>,
     ) -> Result<Response<ToggleResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ToggleRequest {} = request.into_inner();
+
+        let active = streaming::active();
+
+        if active {
+            streaming::stop();
+        } else {
+            streaming::start();
+        }
+
+        Ok(Response::new(ToggleResponse { active: !active }))
     }
 
     async fn start(
         &self,
         request: Request<StartRequest>,
     ) -> Result<Response<StartResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StartRequest {} = request.into_inner();
+
+        precondition!(!streaming::active(), "streaming already active");
+
+        streaming::start();
+
+        Ok(Response::new(StartResponse {}))
     }
 
     async fn stop(&self, request: Request<StopRequest>) -> Result<Response<StopResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let StopRequest {} = request.into_inner();
+
+        precondition!(streaming::active(), "streaming already inactive");
+
+        streaming::stop();
+
+        Ok(Response::new(StopResponse {}))
     }
 
     async fn send_captions(
         &self,
         request: Request<SendCaptionsRequest>,
     ) -> Result<Response<SendCaptionsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SendCaptionsRequest { captions, duration } = request.into_inner();
+
+        send_captions(&captions, duration)?;
+
+        Ok(Response::new(SendCaptionsResponse {}))
+    }
+
+    async fn stream_captions(
+        &self,
+        request: Request<Streaming<StreamCaptionsRequest>>,
+    ) -> Result<Response<StreamCaptionsResponse>, Status> {
+        let mut stream = request.into_inner();
+        let mut count = 0;
+
+        while let Some(StreamCaptionsRequest { captions, duration }) = stream.message().await? {
+            send_captions(&captions, duration)?;
+            count += 1;
+        }
+
+        Ok(Response::new(StreamCaptionsResponse { count }))
     }
 
     async fn bitrate(
         &self,
         request: Request<BitrateRequest>,
     ) -> Result<Response<BitrateResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let BitrateRequest {} = request.into_inner();
+
+        let profile = frontend::profile_config();
+        let (bitrate, mode) = if util::is_advanced_output(&profile) {
+            let bitrate = stream_encoder_bitrate(&profile).ok_or_else(precondition_fn!(
+                "no bitrate configured for the stream encoder"
+            ))?;
+
+            (bitrate, OutputMode::Advanced)
+        } else {
+            let bitrate = profile
+                .int("SimpleOutput", "VBitrate")
+                .or_else(|| profile.default_int("SimpleOutput", "VBitrate"))
+                .ok_or_else(precondition_fn!(
+                    "no bitrate configured for the simple output"
+                ))?;
+
+            (bitrate, OutputMode::Simple)
+        };
+
+        Ok(Response::new(BitrateResponse {
+            bitrate: bitrate as u32,
+            mode: mode.into(),
+        }))
     }
 
     async fn set_bitrate(
         &self,
         request: Request<SetBitrateRequest>,
     ) -> Result<Response<SetBitrateResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetBitrateRequest { bitrate } = request.into_inner();
+
+        precondition!(bitrate > 0, "bitrate must be greater than zero");
+
+        let profile = frontend::profile_config();
+        let mode = if util::is_advanced_output(&profile) {
+            // Update the same settings file that the bitrate is read from, so other values that
+            // differ from the encoder's defaults are kept.
+            let mut settings = load_stream_encoder_settings().unwrap_or_default();
+            settings.set_int("bitrate", bitrate.into());
+            settings
+                .save_json_safe(&stream_encoder_path(), "tmp", "bak")
+                .map_err(|e| Status::internal(e.to_string()))?;
+
+            OutputMode::Advanced
+        } else {
+            profile.set_int("SimpleOutput", "VBitrate", bitrate.into());
+            profile
+                .save_safe("tmp", None)
+                .map_err(|e| Status::internal(e.to_string()))?;
+
+            OutputMode::Simple
+        };
+
+        // Only settings of the running stream must be updated, as the encoder picks up the
+        // profile settings when the stream is started the next time.
+        let live = streaming::output()
+            .filter(|output| output.active())
+            .and_then(|output| {
+                let encoder = output.video_encoder()?;
+                let mut settings = Data::new();
+                settings.set_int("bitrate", bitrate.into());
+                encoder.update(&settings);
+
+                Some(())
+            })
+            .is_some();
+
+        Ok(Response::new(SetBitrateResponse {
+            mode: mode.into(),
+            live,
+        }))
     }
 
     async fn settings(
         &self,
         request: Request<SettingsRequest>,
     ) -> Result<Response<SettingsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SettingsRequest {} = request.into_inner();
+
+        let service = streaming::service();
+
+        Ok(Response::new(SettingsResponse {
+            settings: Some(Settings {
+                r#type: service.id(),
+                settings: service.settings().to_json(),
+            }),
+        }))
     }
 
     async fn set_settings(
         &self,
         request: Request<SetSettingsRequest>,
     ) -> Result<Response<SetSettingsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetSettingsRequest { settings } = request.into_inner();
+        let Settings {
+            r#type: ty,
+            settings,
+        } = settings.ok_or_else(|| Status::invalid_argument("settings must be specified"))?;
+
+        precondition!(!ty.is_empty(), "service type mustn't be empty");
+        precondition!(
+            !streaming::active(),
+            "stream service can't be changed while streaming"
+        );
+
+        let settings = Data::from_json(&settings)
+            .map_err(|e| Status::invalid_argument(format!("invalid JSON settings: {e:?}")))?;
+        let current = streaming::service();
+
+        if current.id() == ty {
+            current.update(&settings);
+        } else {
+            precondition!(
+                obs::service::list_service_types().contains(&ty),
+                "`{ty}` isn't a valid service type"
+            );
+
+            let hotkeys = hotkeys::save_service(&current);
+            let service = Service::create(&ty, CUSTOM_SERVICE_NAME, &settings, hotkeys.as_ref())
+                .ok_or_else(|| Status::internal(format!("failed creating `{ty}` service")))?;
+
+            streaming::set_service(&service);
+        }
+
+        streaming::save();
+
+        Ok(Response::new(SetSettingsResponse {}))
+    }
+}
+
+fn stream_encoder_path() -> String {
+    profiles::current_path()
+        .join(STREAM_ENCODER_FILE)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn load_stream_encoder_settings() -> Option<Data<'static>> {
+    Data::from_json_file_safe(&stream_encoder_path(), "bak").ok()
+}
+
+/// Bitrate of the advanced output's stream encoder. The settings file only contains values that
+/// differ from the encoder's defaults, so fall back to the default bitrate of the configured
+/// encoder type if it isn't set.
+fn stream_encoder_bitrate(profile: &Config) -> Option<i64> {
+    load_stream_encoder_settings()
+        .and_then(|settings| settings.int("bitrate"))
+        .or_else(|| {
+            let id = profile
+                .string("AdvOut", "Encoder")
+                .or_else(|| profile.default_string("AdvOut", "Encoder"))?;
+            obs::encoder::defaults(&id)?.default_int("bitrate")
+        })
+}
+
+fn send_captions(captions: &str, duration: Option<prost_types::Duration>) -> Result<(), Status> {
+    validate_captions(captions)?;
+
+    let duration = duration
+        .map(std::time::Duration::try_from)
+        .transpose()
+        .map_err(|e| Status::invalid_argument(format!("invalid duration: {e:?}")))?
+        .unwrap_or_default();
+
+    let output = streaming::output()
+        .filter(|output| output.active())
+        .ok_or_else(precondition_fn!("streaming inactive"))?;
+
+    output.output_caption_text2(captions, duration.as_secs_f64());
+
+    Ok(())
+}
+
+fn validate_captions(captions: &str) -> Result<(), Status> {
+    precondition!(!captions.is_empty(), "captions mustn't be empty");
+    precondition!(
+        captions.chars().count() <= MAX_CAPTION_LENGTH,
+        "captions mustn't be longer than {MAX_CAPTION_LENGTH} characters"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[test]
+    fn captions_length() {
+        assert!(validate_captions("hello world").is_ok());
+        assert!(validate_captions(&"a".repeat(MAX_CAPTION_LENGTH)).is_ok());
+        // Counted in characters, not bytes.
+        assert!(validate_captions(&"ä".repeat(MAX_CAPTION_LENGTH)).is_ok());
+
+        for captions in [String::new(), "a".repeat(MAX_CAPTION_LENGTH + 1)] {
+            let status = validate_captions(&captions).unwrap_err();
+            assert_eq!(Code::FailedPrecondition, status.code());
+        }
     }
 }
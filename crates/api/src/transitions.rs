@@ -1,84 +1,337 @@
-use tonic::{Request, Response, Status};
+use std::{ops::RangeInclusive, time::Duration};
+
+use obs::{
+    data::Data,
+    frontend::{preview_mode, transitions},
+    source::Source,
+};
+use tonic::{Request, Response, Status, Streaming};
 
 pub use self::transitions_service_server::TransitionsServiceServer;
+use crate::{precondition, precondition_fn, util};
 
 tonic::include_proto!("transitions.v1");
 
+/// Allowed range for transition durations, the same as in the OBS UI.
+const DURATION_RANGE: RangeInclusive<Duration> =
+    Duration::from_millis(50)..=Duration::from_millis(20_000);
+/// Amount of steps of the T-bar in the OBS UI, that normalized positions are scaled to.
+const T_BAR_PRECISION: f32 = 1024.0;
+
+impl From<Source<'_>> for Transition {
+    fn from(value: Source<'_>) -> Self {
+        Self {
+            name: value.name(),
+            kind: value.id(),
+            fixed: value.transition_fixed(),
+            configurable: value.configurable(),
+        }
+    }
+}
+
 pub struct TransitionsService;
 
 #[tonic::async_trait]
 impl transitions_service_server::TransitionsService for TransitionsService {
     async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ListRequest {} = request.into_inner();
+
+        Ok(Response::new(ListResponse {
+            current: transitions::current().name(),
+            transitions: transitions::list().into_iter().map(Into::into).collect(),
+        }))
     }
 
     async fn current(
         &self,
         request: Request<CurrentRequest>,
     ) -> Result<Response<CurrentResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let CurrentRequest {} = request.into_inner();
+
+        let current = transitions::current();
+        let fixed = current.transition_fixed();
+        let configurable = current.configurable();
+
+        Ok(Response::new(CurrentResponse {
+            name: current.name(),
+            kind: current.id(),
+            fixed,
+            duration: (!fixed).then(|| util::duration_to_proto(transitions::duration())),
+            configurable,
+            settings: configurable.then(|| current.settings().to_json()),
+        }))
     }
 
     async fn set_current(
         &self,
         request: Request<SetCurrentRequest>,
     ) -> Result<Response<SetCurrentResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetCurrentRequest { name } = request.into_inner();
+        let transition = find_transition(&name)?;
+
+        transitions::set_current(&transition);
+
+        Ok(Response::new(SetCurrentResponse {}))
     }
 
     async fn set_current_duration(
         &self,
         request: Request<SetCurrentDurationRequest>,
     ) -> Result<Response<SetCurrentDurationResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetCurrentDurationRequest { duration } = request.into_inner();
+        let duration = duration
+            .ok_or_else(|| Status::invalid_argument("duration must be specified"))
+            .and_then(|duration| {
+                Duration::try_from(duration)
+                    .map_err(|e| Status::invalid_argument(format!("invalid duration: {e:?}")))
+            })?;
+
+        precondition!(
+            DURATION_RANGE.contains(&duration),
+            "duration must be between {}ms and {}ms",
+            DURATION_RANGE.start().as_millis(),
+            DURATION_RANGE.end().as_millis()
+        );
+        precondition!(
+            !transitions::current().transition_fixed(),
+            "current transition has a fixed duration"
+        );
+
+        transitions::set_duration(
+            obs::Duration::try_from(duration)
+                .map_err(|e| Status::invalid_argument(format!("invalid duration: {e:?}")))?,
+        );
+
+        Ok(Response::new(SetCurrentDurationResponse {}))
     }
 
     async fn settings(
         &self,
         request: Request<SettingsRequest>,
     ) -> Result<Response<SettingsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SettingsRequest { name } = request.into_inner();
+        let transition = find_transition(&name)?;
+
+        precondition!(
+            transition.configurable(),
+            "`{name}` doesn't have any settings"
+        );
+
+        Ok(Response::new(SettingsResponse {
+            settings: transition.settings().to_json(),
+        }))
     }
 
     async fn set_settings(
         &self,
         request: Request<SetSettingsRequest>,
     ) -> Result<Response<SetSettingsResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetSettingsRequest {
+            name,
+            settings,
+            overlay,
+        } = request.into_inner();
+        let overlay = overlay.unwrap_or(true);
+
+        let transition = find_transition(&name)?;
+
+        precondition!(
+            transition.configurable(),
+            "`{name}` doesn't have any settings"
+        );
+
+        let settings = Data::from_json(&settings)
+            .map_err(|e| Status::invalid_argument(format!("invalid JSON settings: {e:?}")))?;
+
+        if overlay {
+            transition.update(settings);
+        } else {
+            transition.reset_settings(settings);
+        }
+
+        transition.update_properties();
+
+        Ok(Response::new(SetSettingsResponse {}))
     }
 
     async fn release_t_bar(
         &self,
         request: Request<ReleaseTBarRequest>,
     ) -> Result<Response<ReleaseTBarResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let ReleaseTBarRequest {} = request.into_inner();
+
+        ensure_t_bar_control()?;
+
+        transitions::release_tbar();
+
+        Ok(Response::new(ReleaseTBarResponse {}))
     }
 
     async fn set_t_bar_position(
         &self,
         request: Request<SetTBarPositionRequest>,
     ) -> Result<Response<SetTBarPositionResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let SetTBarPositionRequest { position, release } = request.into_inner();
+
+        set_t_bar_position(position)?;
+
+        if release.unwrap_or(true) {
+            transitions::release_tbar();
+        }
+
+        Ok(Response::new(SetTBarPositionResponse {}))
+    }
+
+    async fn stream_t_bar_position(
+        &self,
+        request: Request<Streaming<StreamTBarPositionRequest>>,
+    ) -> Result<Response<StreamTBarPositionResponse>, Status> {
+        let mut stream = request.into_inner();
+        let mut moved = false;
+
+        let result = async {
+            while let Some(StreamTBarPositionRequest { position }) = stream.message().await? {
+                set_t_bar_position(position)?;
+                moved = true;
+            }
+
+            Ok::<_, Status>(())
+        }
+        .await;
+
+        // Always release the T-bar again, so it isn't left in a half-way position if the client
+        // disconnects or sends invalid positions.
+        if moved {
+            transitions::release_tbar();
+        }
+
+        result.map(|()| Response::new(StreamTBarPositionResponse {}))
     }
 
     async fn trigger_studio_mode_transition(
         &self,
         request: Request<TriggerStudioModeTransitionRequest>,
     ) -> Result<Response<TriggerStudioModeTransitionResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let TriggerStudioModeTransitionRequest {} = request.into_inner();
+
+        precondition!(preview_mode::active(), "studio mode not active");
+
+        preview_mode::trigger_transition();
+
+        Ok(Response::new(TriggerStudioModeTransitionResponse {}))
     }
 
     async fn create(
         &self,
         request: Request<CreateRequest>,
     ) -> Result<Response<CreateResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let CreateRequest {
+            kind,
+            name,
+            settings,
+        } = request.into_inner();
+
+        precondition!(!name.is_empty(), "transition name mustn't be empty");
+        precondition!(
+            obs::source::list_transition_types().contains(&kind),
+            "`{kind}` isn't a valid transition kind"
+        );
+        precondition!(
+            !transitions::list().iter().any(|t| t.name() == name),
+            "`{name}` already exists"
+        );
+
+        if let Some(settings) = settings {
+            Data::from_json(&settings)
+                .map_err(|e| Status::invalid_argument(format!("invalid JSON settings: {e:?}")))?;
+        }
+
+        // A private transition could be created with `Source::create_private`, but it wouldn't
+        // show up anywhere, as the frontend only lists transitions from the scene collection.
+        Err(Status::unimplemented(format!(
+            "`{name}` can't be created, as the OBS frontend API provides no way of adding \
+             transitions to the frontend's transition list"
+        )))
     }
 
     async fn delete(
         &self,
         request: Request<DeleteRequest>,
     ) -> Result<Response<DeleteResponse>, Status> {
-        Err(Status::unimplemented("not implemented!"))
+        let DeleteRequest { name } = request.into_inner();
+        find_transition(&name)?;
+
+        precondition!(
+            transitions::current().name() != name,
+            "the current transition can't be deleted"
+        );
+
+        Err(Status::unimplemented(format!(
+            "`{name}` can't be deleted, as the OBS frontend API provides no way of removing \
+             transitions from the frontend's transition list"
+        )))
+    }
+}
+
+/// Find a transition by name, in the list of transitions known to the frontend.
+fn find_transition(name: &str) -> Result<Source<'static>, Status> {
+    precondition!(!name.is_empty(), "transition name mustn't be empty");
+
+    transitions::list()
+        .into_iter()
+        .find(|transition| transition.name() == name)
+        .ok_or_else(precondition_fn!("`{name}` doesn't exist"))
+}
+
+/// Make sure the T-bar can be controlled, which requires studio mode and a current transition
+/// without a fixed duration.
+fn ensure_t_bar_control() -> Result<(), Status> {
+    precondition!(preview_mode::active(), "studio mode not active");
+    precondition!(
+        !transitions::current().transition_fixed(),
+        "current transition doesn't support T-bar control"
+    );
+
+    Ok(())
+}
+
+fn set_t_bar_position(position: f32) -> Result<(), Status> {
+    let position = scale_t_bar_position(position)?;
+    ensure_t_bar_control()?;
+
+    transitions::set_tbar_position(position);
+
+    Ok(())
+}
+
+/// Scale a normalized T-bar position to the steps of the T-bar in the OBS UI.
+fn scale_t_bar_position(position: f32) -> Result<i32, Status> {
+    precondition!(
+        (0.0..=1.0).contains(&position),
+        "position must be between 0.0 and 1.0"
+    );
+
+    Ok((position * T_BAR_PRECISION) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[test]
+    fn t_bar_position_scaling() {
+        assert_eq!(0, scale_t_bar_position(0.0).unwrap());
+        assert_eq!(512, scale_t_bar_position(0.5).unwrap());
+        assert_eq!(1024, scale_t_bar_position(1.0).unwrap());
+    }
+
+    #[test]
+    fn t_bar_position_out_of_range() {
+        for position in [-0.1, 1.1, f32::NAN, f32::INFINITY] {
+            let status = scale_t_bar_position(position).unwrap_err();
+            assert_eq!(Code::FailedPrecondition, status.code());
+        }
     }
 }
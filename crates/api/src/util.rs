@@ -1,5 +1,7 @@
+use obs::{config::Config, output::Output};
 use prost_types::{
-    value::Kind as ProtoKind, ListValue as ProtoList, Struct as ProtoStruct, Value as ProtoValue,
+    value::Kind as ProtoKind, Duration as ProtoDuration, ListValue as ProtoList,
+    Struct as ProtoStruct, Value as ProtoValue,
 };
 use serde_json::{Number as JsonNumber, Value as JsonValue};
 
@@ -68,3 +70,61 @@ fn kind_to_json(value: ProtoKind) -> JsonValue {
         ),
     }
 }
+
+pub fn duration_to_proto(value: obs::Duration) -> ProtoDuration {
+    ProtoDuration {
+        seconds: value.whole_seconds(),
+        nanos: value.subsec_nanoseconds(),
+    }
+}
+
+/// Convert a protobuf duration into a signed duration. Unlike the conversion into the standard
+/// library's duration, this keeps negative values intact.
+pub fn duration_from_proto(value: ProtoDuration) -> Option<obs::Duration> {
+    obs::Duration::seconds(value.seconds)
+        .checked_add(obs::Duration::nanoseconds(i64::from(value.nanos)))
+}
+
+/// Whether the profile uses the advanced output mode, instead of the simple one.
+pub fn is_advanced_output(profile: &Config) -> bool {
+    profile.string("Output", "Mode").as_deref() == Some("Advanced")
+}
+
+/// Directory that recordings are saved to, which is stored under different keys, depending on the
+/// output mode and, in advanced mode, whether the custom FFmpeg output is used.
+pub fn recording_directory(profile: &Config) -> Option<String> {
+    if !is_advanced_output(profile) {
+        profile.string("SimpleOutput", "FilePath")
+    } else if profile.string("AdvOut", "RecType").as_deref() == Some("FFmpeg") {
+        profile.string("AdvOut", "FFFilePath")
+    } else {
+        profile.string("AdvOut", "RecFilePath")
+    }
+}
+
+/// Calculate for how long the output has been running, based on the amount of frames it produced.
+pub fn output_duration(output: &Output<'_>) -> obs::Duration {
+    if !output.active() {
+        return obs::Duration::ZERO;
+    }
+
+    let frame_time = output.video().frame_time();
+    let total_frames = u64::from(output.total_frames());
+
+    obs::Duration::nanoseconds((total_frames * frame_time) as i64)
+}
+
+/// Format the duration as timecode in the form of `HH:MM:SS.mmm`.
+pub fn timecode(value: obs::Duration) -> String {
+    let ms = value.whole_milliseconds();
+    let secs = ms / 1000;
+    let minutes = secs / 60;
+
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        minutes / 60,
+        minutes % 60,
+        secs % 60,
+        ms % 1000
+    )
+}
@@ -18,7 +18,11 @@ use obs::{
     module_use_default_locale, Plugin,
 };
 use tokio::sync::watch;
-use tonic::{codec::CompressionEncoding, transport::Server};
+use tokio_stream::StreamExt;
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{server::TcpIncoming, Server},
+};
 
 macro_rules! new_service {
     ($server:ident, $service:expr) => {
@@ -64,8 +68,10 @@ impl Plugin for ObsRemotePlugin {
                 .build()
                 .unwrap()
                 .block_on(async {
-                    let ipv4 = run_server(signal.clone(), false);
-                    let ipv6 = run_server(signal, true);
+                    let events = api::EventsService::new(signal.clone());
+                    let metrics = api::Metrics::new();
+                    let ipv4 = run_server(signal.clone(), events.clone(), metrics.clone(), false);
+                    let ipv6 = run_server(signal, events, metrics, true);
 
                     tokio::try_join!(ipv4, ipv6).map(|_| ())
                 })
@@ -111,7 +117,12 @@ fn init_logger() -> bool {
     }
 }
 
-async fn run_server(mut signal: watch::Receiver<()>, ipv6: bool) -> Result<()> {
+async fn run_server(
+    mut signal: watch::Receiver<()>,
+    events: api::EventsService,
+    metrics: api::Metrics,
+    ipv6: bool,
+) -> Result<()> {
     #[allow(clippy::wildcard_imports)]
     use api::*;
 
@@ -129,14 +140,29 @@ async fn run_server(mut signal: watch::Receiver<()>, ipv6: bool) -> Result<()> {
 
     info!("OBS Remote server starting up at {} ...", addr);
 
+    // Disable Nagle's algorithm on accepted connections, so small unary responses aren't delayed.
+    let incoming = TcpIncoming::bind(addr)?.with_nodelay(Some(true)).map({
+        let metrics = metrics.clone();
+        move |stream| {
+            if stream.is_ok() {
+                metrics.connection_accepted();
+            }
+            stream
+        }
+    });
+
     let result = Server::builder()
         .accept_http1(true)
         .layer(tonic_web::GrpcWebLayer::new())
+        .layer(tonic::service::interceptor(metrics.interceptor()))
         .add_service(reflection)
         .add_service(new_service!(ConfigServiceServer, ConfigService))
-        .add_service(new_service!(EventsServiceServer, EventsService))
+        .add_service(new_service!(EventsServiceServer, events.clone()))
         .add_service(new_service!(FiltersServiceServer, FiltersService))
-        .add_service(new_service!(GeneralServiceServer, GeneralService))
+        .add_service(new_service!(
+            GeneralServiceServer,
+            GeneralService::new(events, metrics.clone(), signal.clone())
+        ))
         .add_service(new_service!(HotkeysServiceServer, HotkeysService))
         .add_service(new_service!(InputsServiceServer, InputsService))
         .add_service(new_service!(MediaInputsServiceServer, MediaInputsService))
@@ -155,7 +181,7 @@ async fn run_server(mut signal: watch::Receiver<()>, ipv6: bool) -> Result<()> {
         .add_service(new_service!(StreamingServiceServer, StreamingService))
         .add_service(new_service!(TransitionsServiceServer, TransitionsService))
         .add_service(new_service!(VirtualCamServiceServer, VirtualCamService))
-        .serve_with_shutdown(addr, async {
+        .serve_with_incoming_shutdown(incoming, async {
             signal.changed().await.ok();
         })
         .await;
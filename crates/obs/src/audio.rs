@@ -1,6 +1,9 @@
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{ffi::c_void, marker::PhantomData, ptr::NonNull};
 
-use crate::util::FfiToString;
+use crate::{source::Source, util::FfiToString};
+
+/// Maximum amount of audio channels, that OBS can handle.
+pub const MAX_AUDIO_CHANNELS: usize = 8;
 
 pub struct Audio<'a> {
     raw: NonNull<libobs_sys::audio_t>,
@@ -171,6 +174,180 @@ impl Format {
     }
 }
 
+/// Type of fader, that defines how a fader position is mapped to a volume level.
+#[derive(Clone, Copy, Debug)]
+pub enum FaderType {
+    /// Simple cubic fader, mapping the position to a mul value with `x^3`.
+    Cubic,
+    /// Fader compliant to IEC 60-268-18.
+    Iec,
+    /// Logarithmic fader.
+    Log,
+}
+
+impl FaderType {
+    const fn to_native(self) -> libobs_sys::obs_fader_type::Type {
+        use libobs_sys::obs_fader_type::*;
+
+        match self {
+            Self::Cubic => OBS_FADER_CUBIC,
+            Self::Iec => OBS_FADER_IEC,
+            Self::Log => OBS_FADER_LOG,
+        }
+    }
+}
+
+/// Method that a [`Volmeter`] uses to measure the peak values.
+#[derive(Clone, Copy, Debug)]
+pub enum PeakMeterType {
+    /// Maximum of all samples.
+    Sample,
+    /// Maximum of the inter-samples, which is more accurate but also more expensive to calculate.
+    True,
+}
+
+impl PeakMeterType {
+    const fn to_native(self) -> libobs_sys::obs_peak_meter_type::Type {
+        use libobs_sys::obs_peak_meter_type::*;
+
+        match self {
+            Self::Sample => SAMPLE_PEAK_METER,
+            Self::True => TRUE_PEAK_METER,
+        }
+    }
+}
+
+/// Volume meter, that reports the audio levels of an attached source.
+///
+/// The meter only keeps a weak reference to the source and detaches automatically once the
+/// source is destroyed.
+pub struct Volmeter {
+    raw: NonNull<libobs_sys::obs_volmeter_t>,
+    #[allow(clippy::vec_box)]
+    callbacks: Vec<Box<VolmeterCallback>>,
+}
+
+struct VolmeterCallback {
+    volmeter: NonNull<libobs_sys::obs_volmeter_t>,
+    handler: Box<dyn Fn(&VolmeterLevels) + Send>,
+}
+
+impl Drop for Volmeter {
+    fn drop(&mut self) {
+        for callback in &mut self.callbacks {
+            unsafe {
+                libobs_sys::obs_volmeter_remove_callback(
+                    self.raw.as_ptr(),
+                    Some(volmeter_callback),
+                    (&mut **callback as *mut VolmeterCallback).cast(),
+                );
+            }
+        }
+
+        unsafe { libobs_sys::obs_volmeter_destroy(self.raw.as_ptr()) };
+    }
+}
+
+unsafe impl Send for Volmeter {}
+
+unsafe impl Sync for Volmeter {}
+
+impl Volmeter {
+    pub fn new(ty: FaderType) -> Self {
+        let raw = unsafe { libobs_sys::obs_volmeter_create(ty.to_native()) };
+
+        Self {
+            raw: unsafe { NonNull::new_unchecked(raw) },
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Attach the meter to the given source, replacing any previously attached source.
+    pub fn attach_source(&mut self, source: &Source<'_>) -> bool {
+        unsafe { libobs_sys::obs_volmeter_attach_source(self.raw.as_ptr(), source.as_ptr()) }
+    }
+
+    pub fn detach_source(&mut self) {
+        unsafe { libobs_sys::obs_volmeter_detach_source(self.raw.as_ptr()) };
+    }
+
+    pub fn set_peak_meter_type(&mut self, ty: PeakMeterType) {
+        unsafe { libobs_sys::obs_volmeter_set_peak_meter_type(self.raw.as_ptr(), ty.to_native()) };
+    }
+
+    /// Amount of audio channels, that are currently measured.
+    pub fn channels(&self) -> usize {
+        unsafe { libobs_sys::obs_volmeter_get_nr_channels(self.raw.as_ptr()) as usize }
+    }
+
+    /// Add a callback that is invoked from the audio thread, whenever new levels were measured.
+    /// It stays registered until the meter is dropped.
+    pub fn add_callback<C: Fn(&VolmeterLevels) + Send + 'static>(&mut self, handler: C) {
+        let mut callback = Box::new(VolmeterCallback {
+            volmeter: self.raw,
+            handler: Box::new(handler),
+        });
+
+        unsafe {
+            libobs_sys::obs_volmeter_add_callback(
+                self.raw.as_ptr(),
+                Some(volmeter_callback),
+                (&mut *callback as *mut VolmeterCallback).cast(),
+            );
+        }
+
+        self.callbacks.push(callback);
+    }
+}
+
+unsafe extern "C" fn volmeter_callback(
+    param: *mut c_void,
+    magnitude: *const f32,
+    peak: *const f32,
+    input_peak: *const f32,
+) {
+    let callback = unsafe { &*param.cast::<VolmeterCallback>() };
+    let channels = unsafe { libobs_sys::obs_volmeter_get_nr_channels(callback.volmeter.as_ptr()) };
+
+    let levels = unsafe {
+        VolmeterLevels {
+            channels: (channels as usize).min(MAX_AUDIO_CHANNELS),
+            magnitude: *magnitude.cast::<[f32; MAX_AUDIO_CHANNELS]>(),
+            peak: *peak.cast::<[f32; MAX_AUDIO_CHANNELS]>(),
+            input_peak: *input_peak.cast::<[f32; MAX_AUDIO_CHANNELS]>(),
+        }
+    };
+
+    (callback.handler)(&levels);
+}
+
+/// Audio levels of a single measurement from a [`Volmeter`], in dBFS for each channel.
+#[derive(Clone, Copy, Debug)]
+pub struct VolmeterLevels {
+    channels: usize,
+    magnitude: [f32; MAX_AUDIO_CHANNELS],
+    peak: [f32; MAX_AUDIO_CHANNELS],
+    input_peak: [f32; MAX_AUDIO_CHANNELS],
+}
+
+impl VolmeterLevels {
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn magnitude(&self) -> &[f32] {
+        &self.magnitude[..self.channels]
+    }
+
+    pub fn peak(&self) -> &[f32] {
+        &self.peak[..self.channels]
+    }
+
+    pub fn input_peak(&self) -> &[f32] {
+        &self.input_peak[..self.channels]
+    }
+}
+
 /// Convert a volume from mul/amplitude to decibel.
 pub fn mul_to_db(volume: f32) -> f32 {
     unsafe { libobs_sys::obs_mul_to_db(volume) }
@@ -97,6 +97,21 @@ impl Calldata {
         success.then_some(val)
     }
 
+    pub fn set_string(&mut self, name: &str, value: &str) {
+        let name = name.cstr();
+        let value = value.cstr();
+        let value = value.as_bytes_with_nul();
+
+        unsafe {
+            libobs_sys::calldata_set_data(
+                self.raw.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr().cast(),
+                value.len(),
+            );
+        }
+    }
+
     fn ptr<T>(&self, name: &str) -> Option<NonNull<T>> {
         let name = name.cstr();
         let mut val = ptr::null_mut::<c_void>();
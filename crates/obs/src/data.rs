@@ -62,6 +62,39 @@ impl Data<'_> {
         Ok(Self::from_raw(raw))
     }
 
+    /// Load data from a JSON file, falling back to the backup file if the main file can't be read.
+    pub fn from_json_file_safe(file: &str, backup_ext: &str) -> Result<Self> {
+        let file = file.cstr();
+        let backup_ext = backup_ext.cstr();
+        let raw = unsafe {
+            libobs_sys::obs_data_create_from_json_file_safe(file.as_ptr(), backup_ext.as_ptr())
+        };
+
+        ensure!(!raw.is_null(), "failed loading JSON file");
+
+        Ok(Self::from_raw(raw))
+    }
+
+    /// Save the data as JSON file, by first writing to a temporary file and keeping a backup of
+    /// the previous file.
+    pub fn save_json_safe(&self, file: &str, temp_ext: &str, backup_ext: &str) -> Result<()> {
+        let file = file.cstr();
+        let temp_ext = temp_ext.cstr();
+        let backup_ext = backup_ext.cstr();
+
+        let saved = unsafe {
+            libobs_sys::obs_data_save_json_safe(
+                self.raw.as_ptr(),
+                file.as_ptr(),
+                temp_ext.as_ptr(),
+                backup_ext.as_ptr(),
+            )
+        };
+
+        ensure!(saved, "failed saving JSON file");
+        Ok(())
+    }
+
     pub fn item_by_name(&self, name: &str) -> Option<DataItem> {
         let name = name.cstr();
         let raw = unsafe { libobs_sys::obs_data_item_byname(self.raw.as_ptr(), name.as_ptr()) };
@@ -82,6 +115,16 @@ impl Data<'_> {
         self.get(name, libobs_sys::obs_data_get_int)
     }
 
+    /// Get the default value of an integer, as opposed to [`Self::int`] that only returns values
+    /// that were explicitly set.
+    pub fn default_int(&self, name: &str) -> Option<i64> {
+        let name = name.cstr();
+
+        unsafe { libobs_sys::obs_data_has_default_value(self.raw.as_ptr(), name.as_ptr()) }.then(
+            || unsafe { libobs_sys::obs_data_get_default_int(self.raw.as_ptr(), name.as_ptr()) },
+        )
+    }
+
     pub fn set_bool(&mut self, name: &str, value: bool) {
         self.set(name, value, libobs_sys::obs_data_set_bool)
     }
@@ -1,6 +1,9 @@
 use std::{ffi::c_void, marker::PhantomData, ptr::NonNull};
 
-use crate::util::FfiToString;
+use crate::{
+    data::Data,
+    util::{FfiToString, StringToFfi},
+};
 
 pub struct Encoder<'a> {
     raw: NonNull<libobs_sys::obs_encoder_t>,
@@ -55,6 +58,17 @@ impl Encoder<'_> {
     pub fn frame_size(&self) -> usize {
         unsafe { libobs_sys::obs_encoder_get_frame_size(self.raw.as_ptr()) }
     }
+
+    /// Returns the current settings of the encoder.
+    pub fn settings(&self) -> Data<'_> {
+        Data::from_raw(unsafe { libobs_sys::obs_encoder_get_settings(self.raw.as_ptr()) })
+    }
+
+    /// Updates the settings of the encoder context. Some encoders support changing settings, like
+    /// the bitrate, while they are active.
+    pub fn update(&self, settings: &Data<'_>) {
+        unsafe { libobs_sys::obs_encoder_update(self.raw.as_ptr(), settings.as_ptr()) };
+    }
 }
 
 pub fn list() -> Vec<Encoder<'static>> {
@@ -76,6 +90,14 @@ pub fn list() -> Vec<Encoder<'static>> {
     encoders
 }
 
+/// Get the default settings of an encoder type, or `None` if the type doesn't exist.
+pub fn defaults(id: &str) -> Option<Data<'static>> {
+    let id = id.cstr();
+    let raw = unsafe { libobs_sys::obs_encoder_defaults(id.as_ptr()) };
+
+    (!raw.is_null()).then(|| Data::from_raw(raw))
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug)]
 pub enum EncoderType {
@@ -24,7 +24,7 @@ pub fn split_file() -> bool {
     unsafe { libobs_sys::obs_frontend_recording_split_file() }
 }
 
-pub fn output() -> Output<'static> {
+pub fn output() -> Option<Output<'static>> {
     let raw = unsafe { libobs_sys::obs_frontend_get_recording_output() };
-    Output::from_raw(raw)
+    (!raw.is_null()).then(|| Output::from_raw(raw))
 }
@@ -16,9 +16,9 @@ pub fn save() {
     unsafe { libobs_sys::obs_frontend_save_streaming_service() };
 }
 
-pub fn output() -> Output<'static> {
+pub fn output() -> Option<Output<'static>> {
     let raw = unsafe { libobs_sys::obs_frontend_get_streaming_output() };
-    Output::from_raw(raw)
+    (!raw.is_null()).then(|| Output::from_raw(raw))
 }
 
 pub fn service() -> Service<'static> {
@@ -1482,8 +1482,10 @@ pub fn save_output(output: &Output<'_>) -> Data<'static> {
     Data::from_raw(unsafe { libobs_sys::obs_hotkeys_save_output(output.as_ptr()) })
 }
 
-pub fn save_service(service: &Service<'_>) -> Data<'static> {
-    Data::from_raw(unsafe { libobs_sys::obs_hotkeys_save_service(service.as_ptr()) })
+/// Save the hotkeys of a service, if it has any.
+pub fn save_service(service: &Service<'_>) -> Option<Data<'static>> {
+    let raw = unsafe { libobs_sys::obs_hotkeys_save_service(service.as_ptr()) };
+    (!raw.is_null()).then(|| Data::from_raw(raw))
 }
 
 pub fn save_source(source: &Source<'_>) -> Data<'static> {
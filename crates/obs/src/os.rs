@@ -1,5 +1,37 @@
+use std::ptr::NonNull;
+
 use crate::util::StringToFfi;
 
+/// Tracker for the CPU usage of the current process. Each query reports the usage since the
+/// previous one, so it should be kept around instead of being re-created for every measurement.
+pub struct CpuUsageInfo(NonNull<libobs_sys::os_cpu_usage_info_t>);
+
+// SAFETY: The info only holds plain counters, that aren't tied to the thread that created it.
+unsafe impl Send for CpuUsageInfo {}
+
+impl Drop for CpuUsageInfo {
+    fn drop(&mut self) {
+        unsafe { libobs_sys::os_cpu_usage_info_destroy(self.0.as_ptr()) };
+    }
+}
+
+impl Default for CpuUsageInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CpuUsageInfo {
+    pub fn new() -> Self {
+        Self(unsafe { NonNull::new_unchecked(libobs_sys::os_cpu_usage_info_start()) })
+    }
+
+    /// Get the CPU usage in percent, since the last query or the creation of this tracker.
+    pub fn query(&mut self) -> f64 {
+        unsafe { libobs_sys::os_cpu_usage_info_query(self.0.as_ptr()) }
+    }
+}
+
 pub fn cpu_usage() -> f64 {
     unsafe {
         let info = libobs_sys::os_cpu_usage_info_start();
@@ -148,12 +148,13 @@ impl Output<'_> {
         unsafe { libobs_sys::obs_output_get_total_frames(self.raw.as_ptr()) as u32 }
     }
 
-    // TODO: can be null?
-    /// Returns the current video encoder associated with this output
-    pub fn video_encoder(&self) -> Encoder<'_> {
+    /// Returns the current video encoder associated with this output, if any.
+    ///
+    /// The encoder isn't referenced separately, as [`Encoder`] doesn't release it, but its
+    /// lifetime is bound to this output instead.
+    pub fn video_encoder(&self) -> Option<Encoder<'_>> {
         let raw = unsafe { libobs_sys::obs_output_get_video_encoder(self.raw.as_ptr()) };
-        let raw = unsafe { libobs_sys::obs_encoder_get_ref(raw) };
-        Encoder::from_raw(raw)
+        (!raw.is_null()).then(|| Encoder::from_raw(raw))
     }
 
     pub fn width(&self) -> u32 {
@@ -182,6 +183,11 @@ impl Output<'_> {
         Data::from_raw(unsafe { libobs_sys::obs_output_get_settings(self.raw.as_ptr()) })
     }
 
+    /// Updates the settings for this output context.
+    pub fn update(&self, settings: Data<'_>) {
+        unsafe { libobs_sys::obs_output_update(self.raw.as_ptr(), settings.as_ptr()) };
+    }
+
     pub fn reconnecting(&self) -> bool {
         unsafe { libobs_sys::obs_output_reconnecting(self.raw.as_ptr()) }
     }
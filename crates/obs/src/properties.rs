@@ -3,7 +3,10 @@ use std::{marker::PhantomData, ptr::NonNull};
 use bitflags::bitflags;
 use libobs_sys::obs_group_type::OBS_COMBO_INVALID;
 
-use crate::util::{FfiToString, StringToFfi};
+use crate::{
+    source::Source,
+    util::{FfiToString, StringToFfi},
+};
 
 pub struct Properties<'a> {
     raw: NonNull<libobs_sys::obs_properties_t>,
@@ -421,6 +424,14 @@ impl ButtonProperty<'_> {
     pub fn url(&self) -> Option<String> {
         unsafe { libobs_sys::obs_property_button_url(self.0.raw.as_ptr()) }.into_opt_string()
     }
+
+    /// Run the button's callback for the given source, which must be the one these properties
+    /// were created from. Returns whether the properties need to be refreshed afterwards.
+    pub fn click(&self, source: &Source<'_>) -> bool {
+        unsafe {
+            libobs_sys::obs_property_button_clicked(self.0.raw.as_ptr(), source.as_ptr().cast())
+        }
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
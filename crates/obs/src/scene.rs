@@ -2,7 +2,14 @@ use std::{ffi::c_void, marker::PhantomData, ops::Deref, ptr::NonNull};
 
 use bitflags::bitflags;
 
-use crate::{graphics::Vec2, source::Source, util::StringToFfi, video::ScaleType};
+use crate::{
+    callback::signal::{SceneSignal, SignalHandler},
+    data::Data,
+    graphics::Vec2,
+    source::Source,
+    util::StringToFfi,
+    video::ScaleType,
+};
 
 pub struct Scene<'a> {
     raw: NonNull<libobs_sys::obs_scene_t>,
@@ -35,6 +42,20 @@ impl<'a> Scene<'a> {
         (!raw.is_null()).then(|| Self::from_raw(unsafe { libobs_sys::obs_scene_get_ref(raw) }))
     }
 
+    /// Like [`Self::from_source`], but also accepts group sources.
+    pub fn from_group_or_scene(source: Source<'a>) -> Option<Self> {
+        let raw = unsafe {
+            let raw = libobs_sys::obs_scene_from_source(source.as_ptr());
+            if raw.is_null() {
+                libobs_sys::obs_group_from_source(source.as_ptr())
+            } else {
+                raw
+            }
+        };
+
+        (!raw.is_null()).then(|| Self::from_raw(unsafe { libobs_sys::obs_scene_get_ref(raw) }))
+    }
+
     pub fn list_items(&self) -> Vec<SceneItem<'_>> {
         unsafe extern "C" fn callback(
             _scene: *mut libobs_sys::obs_scene_t,
@@ -63,6 +84,16 @@ impl<'a> Scene<'a> {
         param
     }
 
+    /// Find an item by its ID. Only direct children of the scene are searched, not the items
+    /// within groups.
+    pub fn find_item(&self, id: i64) -> Option<SceneItem<'_>> {
+        let raw = unsafe { libobs_sys::obs_scene_find_sceneitem_by_id(self.raw.as_ptr(), id) };
+        (!raw.is_null()).then(|| {
+            unsafe { libobs_sys::obs_sceneitem_addref(raw) };
+            SceneItem::from_raw(raw)
+        })
+    }
+
     pub fn source(&self) -> Source<'_> {
         Source::from_raw(unsafe {
             let raw = libobs_sys::obs_scene_get_source(self.raw.as_ptr());
@@ -113,15 +144,26 @@ impl<'a> Scene<'a> {
         param.result.unwrap_or_default()
     }
 
-    pub fn add(&mut self, source: &Source<'_>) -> SceneItem<'_> {
+    /// Add the source as new item to the scene. Fails if the source would create a recursion,
+    /// like adding the scene to itself.
+    pub fn add(&mut self, source: &Source<'_>) -> Option<SceneItem<'_>> {
         let raw = unsafe { libobs_sys::obs_scene_add(self.raw.as_ptr(), source.as_ptr()) };
-        unsafe { libobs_sys::obs_sceneitem_addref(raw) };
-        SceneItem::from_raw(raw)
+        (!raw.is_null()).then(|| {
+            unsafe { libobs_sys::obs_sceneitem_addref(raw) };
+            SceneItem::from_raw(raw)
+        })
     }
 
     pub fn prune_sources(&mut self) {
         unsafe { libobs_sys::obs_scene_prune_sources(self.raw.as_ptr()) };
     }
+
+    pub fn signal_handler(&self) -> SignalHandler<SceneSignal> {
+        SignalHandler::from_raw(unsafe {
+            let raw = libobs_sys::obs_scene_get_source(self.raw.as_ptr());
+            libobs_sys::obs_source_get_signal_handler(raw)
+        })
+    }
 }
 
 pub struct SceneItem<'a> {
@@ -235,6 +277,18 @@ impl SceneItem<'_> {
         unsafe { libobs_sys::obs_sceneitem_set_locked(self.raw.as_ptr(), locked) };
     }
 
+    /// Move the item to the given position within its scene, where `0` is the bottom-most
+    /// position.
+    pub fn set_order_position(&self, position: i32) {
+        unsafe { libobs_sys::obs_sceneitem_set_order_position(self.raw.as_ptr(), position) };
+    }
+
+    /// Settings that are attached to the item but not used by libobs itself, like the item color
+    /// of the OBS frontend.
+    pub fn private_settings(&self) -> Data<'_> {
+        Data::from_raw(unsafe { libobs_sys::obs_sceneitem_get_private_settings(self.raw.as_ptr()) })
+    }
+
     pub fn is_group(&self) -> bool {
         unsafe { libobs_sys::obs_sceneitem_is_group(self.raw.as_ptr()) }
     }
@@ -505,7 +559,7 @@ impl BlendingType {
             Self::Screen => OBS_BLEND_SCREEN,
             Self::Multiply => OBS_BLEND_MULTIPLY,
             Self::Lighten => OBS_BLEND_LIGHTEN,
-            Self::Darken => OBS_BLEND_LIGHTEN,
+            Self::Darken => OBS_BLEND_DARKEN,
             Self::Unknown(value) => value as _,
         }
     }
@@ -28,18 +28,27 @@ impl Service<'_> {
         self.raw.as_ptr()
     }
 
-    pub fn create(id: &str, name: &str, settings: &Data<'_>, hotkey_data: &Data<'_>) -> Self {
+    /// Create a new service of the given type. Returns [`None`] if the type doesn't exist or the
+    /// service failed to initialize.
+    pub fn create(
+        id: &str,
+        name: &str,
+        settings: &Data<'_>,
+        hotkey_data: Option<&Data<'_>>,
+    ) -> Option<Self> {
         let id = id.cstr();
         let name = name.cstr();
 
-        Self::from_raw(unsafe {
+        let raw = unsafe {
             libobs_sys::obs_service_create(
                 id.as_ptr(),
                 name.as_ptr(),
                 settings.as_ptr(),
-                hotkey_data.as_ptr(),
+                hotkey_data.map_or(std::ptr::null_mut(), Data::as_ptr),
             )
-        })
+        };
+
+        (!raw.is_null()).then(|| Self::from_raw(raw))
     }
 
     pub fn display_name(id: &str) -> Option<String> {
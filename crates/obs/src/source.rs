@@ -2,7 +2,7 @@ use std::{
     fmt::{self, Display},
     marker::PhantomData,
     os::raw::c_void,
-    ptr::NonNull,
+    ptr::{self, NonNull},
 };
 
 use bitflags::bitflags;
@@ -53,6 +53,57 @@ impl Source<'_> {
         self.raw.as_ptr()
     }
 
+    /// Create a new source of the given type, that is registered globally and can be found by its
+    /// name. The type should be validated beforehand, as OBS creates placeholder sources for
+    /// unknown types.
+    pub fn create(
+        id: &str,
+        name: &str,
+        settings: Option<&Data<'_>>,
+        hotkey_data: Option<&Data<'_>>,
+    ) -> Option<Self> {
+        let id = id.cstr();
+        let name = name.cstr();
+
+        let raw = unsafe {
+            libobs_sys::obs_source_create(
+                id.as_ptr(),
+                name.as_ptr(),
+                settings.map_or(ptr::null_mut(), Data::as_ptr),
+                hotkey_data.map_or(ptr::null_mut(), Data::as_ptr),
+            )
+        };
+
+        (!raw.is_null()).then(|| Self::from_raw(raw))
+    }
+
+    /// Create a new private source of the given type, that isn't registered globally. This is
+    /// what the frontend uses for transitions, for example.
+    pub fn create_private(id: &str, name: &str, settings: Option<&Data<'_>>) -> Option<Self> {
+        let id = id.cstr();
+        let name = name.cstr();
+
+        let raw = unsafe {
+            libobs_sys::obs_source_create_private(
+                id.as_ptr(),
+                name.as_ptr(),
+                settings.map_or(ptr::null_mut(), Data::as_ptr),
+            )
+        };
+
+        (!raw.is_null()).then(|| Self::from_raw(raw))
+    }
+
+    /// Create a new input source, like [`Self::create`], but fails if the type isn't a known
+    /// input type, or the name is already taken by another source.
+    pub fn create_input(id: &str, name: &str, settings: Option<&Data<'_>>) -> Option<Self> {
+        if Self::by_name(name).is_some() || !list_input_types().iter().any(|ty| ty == id) {
+            return None;
+        }
+
+        Self::create(id, name, settings, None)
+    }
+
     pub fn by_name(name: &str) -> Option<Self> {
         let name = name.cstr();
         let raw = unsafe { libobs_sys::obs_get_source_by_name(name.as_ptr()) };
@@ -72,6 +123,13 @@ impl Source<'_> {
         (!raw.is_null()).then(|| Self::from_raw(raw))
     }
 
+    /// Assign a source to one of the global output channels, or clear the channel if `None`.
+    pub fn set_output_channel(channel: u32, source: Option<&Source<'_>>) {
+        unsafe {
+            libobs_sys::obs_set_output_source(channel, source.map_or(ptr::null_mut(), Self::as_ptr))
+        };
+    }
+
     pub fn audio_mixers(&self) -> [bool; 6] {
         let mixers = unsafe { libobs_sys::obs_source_get_audio_mixers(self.raw.as_ptr()) };
         [
@@ -153,6 +211,18 @@ impl Source<'_> {
         })
     }
 
+    /// Properties of this specific source, with its current settings applied. Unlike the
+    /// properties of the source type, these can contain dynamic values, like the list of
+    /// available devices.
+    pub fn properties(&self) -> Option<Properties<'_>> {
+        let raw = unsafe { libobs_sys::obs_source_properties(self.raw.as_ptr()) };
+        (!raw.is_null()).then(|| Properties::from_raw(raw))
+    }
+
+    pub fn set_monitoring_type(&mut self, ty: MonitoringType) {
+        unsafe { libobs_sys::obs_source_set_monitoring_type(self.raw.as_ptr(), ty.to_native()) };
+    }
+
     pub fn name(&self) -> String {
         unsafe { libobs_sys::obs_source_get_name(self.raw.as_ptr()) }.into_string()
     }
@@ -225,6 +295,11 @@ impl Source<'_> {
         unsafe { libobs_sys::obs_source_set_muted(self.raw.as_ptr(), muted) };
     }
 
+    /// Whether the source has any settings that can be configured.
+    pub fn configurable(&self) -> bool {
+        unsafe { libobs_sys::obs_source_configurable(self.raw.as_ptr()) }
+    }
+
     // TODO: Move to transitions area.
     pub fn transition_fixed(&self) -> bool {
         unsafe { libobs_sys::obs_transition_fixed(self.raw.as_ptr()) }
@@ -349,6 +424,51 @@ impl Source<'_> {
             libobs_sys::obs_source_get_signal_handler(self.raw.as_ptr())
         })
     }
+
+    /// Create a weak reference to this source, that doesn't keep it alive.
+    pub fn downgrade(&self) -> WeakSource {
+        WeakSource::from_raw(unsafe { libobs_sys::obs_source_get_weak_source(self.raw.as_ptr()) })
+    }
+}
+
+/// Weak reference to a [`Source`], that can be kept around without preventing the source from
+/// being destroyed.
+pub struct WeakSource {
+    raw: NonNull<libobs_sys::obs_weak_source_t>,
+}
+
+impl Drop for WeakSource {
+    fn drop(&mut self) {
+        unsafe { libobs_sys::obs_weak_source_release(self.raw.as_ptr()) };
+    }
+}
+
+unsafe impl Send for WeakSource {}
+
+unsafe impl Sync for WeakSource {}
+
+impl WeakSource {
+    fn from_raw(raw: *mut libobs_sys::obs_weak_source_t) -> Self {
+        Self {
+            raw: unsafe { NonNull::new_unchecked(raw) },
+        }
+    }
+
+    /// Get a strong reference to the source, if it's still alive.
+    pub fn upgrade(&self) -> Option<Source<'static>> {
+        let raw = unsafe { libobs_sys::obs_weak_source_get_source(self.raw.as_ptr()) };
+        (!raw.is_null()).then(|| Source::from_raw(raw))
+    }
+
+    pub fn expired(&self) -> bool {
+        unsafe { libobs_sys::obs_weak_source_expired(self.raw.as_ptr()) }
+    }
+
+    /// Check whether this weak reference points to the given source. This still works while the
+    /// source is being destroyed.
+    pub fn references(&self, source: &Source<'_>) -> bool {
+        unsafe { libobs_sys::obs_weak_source_references_source(self.raw.as_ptr(), source.as_ptr()) }
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -477,6 +597,16 @@ impl MonitoringType {
             _ => unreachable!(),
         }
     }
+
+    fn to_native(self) -> libobs_sys::obs_monitoring_type::Type {
+        use libobs_sys::obs_monitoring_type::*;
+
+        match self {
+            Self::None => OBS_MONITORING_TYPE_NONE,
+            Self::MonitorOnly => OBS_MONITORING_TYPE_MONITOR_ONLY,
+            Self::MonitorAndOutput => OBS_MONITORING_TYPE_MONITOR_AND_OUTPUT,
+        }
+    }
 }
 
 bitflags! {